@@ -0,0 +1,98 @@
+//! Generates the `Codec` table from the multicodec `table.csv`.
+//!
+//! The table is a curated subset of the upstream table (see `multicodec/README.md`), and the
+//! filtering also works on the full one. Only the codecs tagged `ipld`, and the Filecoin
+//! commitments tagged `filecoin`, are content codecs usable in a CID, so all other rows are
+//! skipped. The generated file is a `build_codec_enum!` invocation included by `src/codec.rs`.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+const TABLE: &str = "multicodec/table.csv";
+
+/// The tags of the rows that are included.
+const TAGS: &[&str] = &["ipld", "filecoin"];
+
+/// Codecs whose variant names predate the generated table and are kept for compatibility.
+const VARIANT_NAMES: &[(&str, &str)] = &[
+    ("dag-pb", "DagProtobuf"),
+    ("dag-cbor", "DagCBOR"),
+    ("dag-json", "DagJSON"),
+    ("dag-jose", "DagJOSE"),
+    ("dag-cose", "DagCOSE"),
+];
+
+/// Name prefixes that are spelled out in variant names.
+const VARIANT_PREFIXES: &[(&str, &str)] = &[("eth-", "Ethereum-")];
+
+fn variant_name(name: &str) -> String {
+    if let Some((_, variant)) = VARIANT_NAMES.iter().find(|(n, _)| *n == name) {
+        return variant.to_string();
+    }
+    let mut name = name.to_string();
+    for (prefix, replacement) in VARIANT_PREFIXES {
+        if let Some(rest) = name.strip_prefix(prefix) {
+            name = format!("{}{}", replacement, rest);
+        }
+    }
+    name.split('-')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn doc(description: &str) -> String {
+    let mut chars = description.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", TABLE);
+
+    let table = fs::read_to_string(TABLE).expect("failed to read the multicodec table");
+    let mut out = String::from("build_codec_enum! {\n");
+    for (i, line) in table.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.splitn(5, ',').map(str::trim).collect();
+        let (name, tag, code, status, description) = match fields[..] {
+            // Descriptions that contain commas are quoted.
+            [name, tag, code, status, description] => {
+                (name, tag, code, status, description.trim_matches('"'))
+            }
+            [name, tag, code, status] => (name, tag, code, status, ""),
+            _ => panic!("{}:{}: malformed row", TABLE, i + 1),
+        };
+        if !TAGS.contains(&tag) || status == "deprecated" {
+            continue;
+        }
+        let code = code.trim_start_matches("0x");
+        u64::from_str_radix(code, 16)
+            .unwrap_or_else(|_| panic!("{}:{}: invalid code", TABLE, i + 1));
+        writeln!(
+            out,
+            "    #[doc = {:?}] 0x{} => {} ({:?}),",
+            doc(description),
+            code,
+            variant_name(name),
+            name
+        )
+        .unwrap();
+    }
+    out.push_str("}\n");
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("codec_table.rs");
+    fs::write(dest, out).expect("failed to write the codec table");
+}
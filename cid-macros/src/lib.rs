@@ -0,0 +1,220 @@
+//! Compile-time parsing of CID literals, re-exported as `cid::cid!` by the `macros` feature of the
+//! `cid` crate.
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use multibase::Base;
+use unsigned_varint::decode as varint_decode;
+
+/// The maximum length of the encoded CID, in bytes, as in `cid::Limits::DEFAULT`.
+const MAX_LEN: usize = 512;
+
+/// The maximum length of the multihash digest, in bytes, as in `cid::Limits::DEFAULT`.
+const MAX_DIGEST_LEN: usize = 128;
+
+/// Parse a CID string literal at compile time.
+///
+/// Expands to a `cid::CidN<S>` constant expression, where `S` is the length of the digest, so it
+/// can initialize `const` and `static` items. Invalid and non-canonical CIDs fail the build, with
+/// the same checks as `Cid::from_str_strict`:
+///
+/// ```
+/// use cid::{cid, CidN};
+///
+/// const EMPTY_DAG_PB: CidN<32> = cid!("bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku");
+/// assert_eq!(
+///     EMPTY_DAG_PB.to_string(),
+///     "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+/// );
+/// ```
+///
+/// ```compile_fail
+/// // The last character is missing.
+/// let typo = cid::cid!("bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyk");
+/// ```
+///
+/// ```compile_fail
+/// // Padded base32 isn't canonical.
+/// let padded = cid::cid!("cafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku======");
+/// ```
+///
+/// ```compile_fail
+/// // Padded base64 isn't canonical, even when there is no padding.
+/// let padded = cid::cid!("MAXASIOOwxEKY/BwUmvv0yJlvuSQnrkHkZJuTTKSVmRt4UrhV");
+/// ```
+///
+/// ```compile_fail
+/// // Base32 lower with an upper case payload.
+/// let upper = cid::cid!("bAFYBEIHDWDCEFGH4DQKJV67UZCMW7OJEE6XEDZDETOJUZJEVTENXQUVYKU");
+/// ```
+#[proc_macro]
+pub fn cid(input: TokenStream) -> TokenStream {
+    let (literal, span) = match parse_literal(input) {
+        Ok(literal) => literal,
+        Err((msg, span)) => return compile_error(msg, span),
+    };
+    match decode(&literal) {
+        Ok((bytes, digest_len)) => expand(&bytes, digest_len),
+        Err(msg) => compile_error(&format!("invalid CID: {}", msg), span),
+    }
+}
+
+/// Return the value and span of the string literal that is the only token of the input.
+fn parse_literal(input: TokenStream) -> Result<(String, Span), (&'static str, Span)> {
+    let mut tokens = input.into_iter();
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(literal)), None) => {
+            let repr = literal.to_string();
+            let value = repr
+                .strip_prefix('"')
+                .and_then(|repr| repr.strip_suffix('"'))
+                .filter(|value| !value.contains('\\'));
+            match value {
+                Some(value) => Ok((value.to_owned(), literal.span())),
+                None => Err(("expected a string literal", literal.span())),
+            }
+        }
+        // Literals passed through a `macro_rules!` macro are wrapped in an undelimited group.
+        (Some(TokenTree::Group(group)), None) if group.delimiter() == Delimiter::None => {
+            parse_literal(group.stream())
+        }
+        (Some(token), _) => Err(("expected a single string literal", token.span())),
+        (None, _) => Err(("expected a CID string literal", Span::call_site())),
+    }
+}
+
+/// Decode and check a CID string, returning its encoded bytes and the length of its digest.
+fn decode(cid_str: &str) -> Result<(Vec<u8>, usize), String> {
+    if cid_str.len() < 2 {
+        return Err("the input is too short".into());
+    }
+    // Base2 is the least dense multibase, with 8 characters per byte plus the base code.
+    if cid_str.len() > MAX_LEN * 8 + 1 {
+        return Err("the input is too long".into());
+    }
+
+    let bytes = if cid_str.starts_with("Qm") {
+        if cid_str.len() != 46 {
+            return Err("CIDv0 strings must be 46 characters".into());
+        }
+        let bytes = Base::Base58Btc
+            .decode(cid_str)
+            .map_err(|err| err.to_string())?;
+        if !is_v0_binary(&bytes) {
+            return Err("not a CIDv0".into());
+        }
+        bytes
+    } else {
+        let code = cid_str.chars().next().expect("at least 2 characters");
+        let base = Base::from_code(code).map_err(|err| err.to_string())?;
+        if is_padded(base) || cid_str.ends_with('=') {
+            return Err("padded multibase encodings are not canonical".into());
+        }
+        let data = &cid_str[code.len_utf8()..];
+        if is_wrong_case(base, data) {
+            return Err(format!("the payload isn't in the case of {:?}", base));
+        }
+        let bytes = base.decode(data).map_err(|err| err.to_string())?;
+        if is_v0_binary(&bytes) {
+            return Err("CIDv0 must be encoded in base58btc, without a multibase prefix".into());
+        }
+        bytes
+    };
+    if bytes.len() > MAX_LEN {
+        return Err("the input is too long".into());
+    }
+
+    let rest = if is_v0_binary(&bytes) {
+        &bytes[..]
+    } else {
+        let (version, rest) = varint(&bytes, "version")?;
+        if version != 1 {
+            return Err(format!("unsupported version {}", version));
+        }
+        let (_, rest) = varint(rest, "codec")?;
+        rest
+    };
+    let (_, rest) = varint(rest, "multihash code")?;
+    let (len, rest) = varint(rest, "digest length")?;
+    if len != rest.len() as u64 {
+        return Err(format!(
+            "the digest is {} bytes, but its length is {}",
+            rest.len(),
+            len
+        ));
+    }
+    let digest_len = rest.len();
+    if digest_len > MAX_DIGEST_LEN {
+        return Err("the multihash digest is too large".into());
+    }
+    Ok((bytes, digest_len))
+}
+
+fn is_padded(base: Base) -> bool {
+    matches!(
+        base,
+        Base::Base32PadLower
+            | Base::Base32PadUpper
+            | Base::Base32HexPadLower
+            | Base::Base32HexPadUpper
+            | Base::Base64Pad
+            | Base::Base64UrlPad
+    )
+}
+
+/// Whether the payload has letters of the other case than the alphabet of the base.
+fn is_wrong_case(base: Base, data: &str) -> bool {
+    match base {
+        Base::Base16Lower | Base::Base32HexLower | Base::Base32Lower | Base::Base36Lower => {
+            data.bytes().any(|b| b.is_ascii_uppercase())
+        }
+        Base::Base16Upper | Base::Base32HexUpper | Base::Base32Upper | Base::Base36Upper => {
+            data.bytes().any(|b| b.is_ascii_lowercase())
+        }
+        _ => false,
+    }
+}
+
+fn is_v0_binary(bytes: &[u8]) -> bool {
+    bytes.len() == 34 && bytes[0] == 0x12 && bytes[1] == 0x20
+}
+
+/// Decode a minimally encoded unsigned varint.
+fn varint<'a>(bytes: &'a [u8], name: &str) -> Result<(u64, &'a [u8]), String> {
+    let (value, rest) =
+        varint_decode::u64(bytes).map_err(|_| format!("invalid {} varint", name))?;
+    let len = bytes.len() - rest.len();
+    if len > 1 && bytes[len - 1] == 0 {
+        return Err(format!("{} varint is not minimally encoded", name));
+    }
+    Ok((value, rest))
+}
+
+/// Return the expression building the `CidN` from its encoded bytes.
+fn expand(bytes: &[u8], digest_len: usize) -> TokenStream {
+    let bytes: Vec<String> = bytes.iter().map(|byte| format!("{:#04x}", byte)).collect();
+    format!(
+        "::cid::CidN::<{}>::from_bytes_const(&[{}])",
+        digest_len,
+        bytes.join(", ")
+    )
+    .parse()
+    .expect("valid tokens")
+}
+
+/// Return a `compile_error!` invocation with the message, pointing at `span`.
+fn compile_error(msg: &str, span: Span) -> TokenStream {
+    let mut message = Literal::string(msg);
+    message.set_span(span);
+    let tokens = vec![
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(
+            Delimiter::Parenthesis,
+            TokenStream::from(TokenTree::Literal(message)),
+        )),
+    ];
+    tokens.into_iter().collect()
+}
@@ -0,0 +1,20 @@
+#![no_main]
+
+use std::convert::TryFrom;
+
+use cid::{Cid, CidRef};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let cid = Cid::try_from(data);
+    // The borrowed parser must agree with the owned one.
+    let cid_ref = CidRef::from_bytes(data);
+    assert_eq!(cid.is_ok(), cid_ref.is_ok());
+
+    if let Ok(cid) = cid {
+        // Non-minimal varints are accepted, so only the decoded CIDs have to match.
+        let bytes = cid.to_bytes();
+        assert_eq!(bytes.len(), cid.encoded_len());
+        assert_eq!(Cid::try_from(bytes.as_slice()).unwrap(), cid);
+    }
+});
@@ -0,0 +1,17 @@
+#![no_main]
+
+use std::convert::TryFrom;
+
+use cid::Cid;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &str| {
+    if let Ok(cid) = Cid::try_from(data) {
+        // The input may differ in case or have an `/ipfs/` prefix, but the string of the decoded
+        // CID must decode to the same CID and be written the same way again.
+        let s = cid.to_string();
+        let reparsed = Cid::try_from(s.as_str()).unwrap();
+        assert_eq!(reparsed, cid);
+        assert_eq!(reparsed.to_string(), s);
+    }
+});
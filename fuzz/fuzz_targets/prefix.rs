@@ -0,0 +1,11 @@
+#![no_main]
+
+use cid::Prefix;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(prefix) = Prefix::new_from_bytes(data) {
+        let bytes = prefix.as_bytes();
+        assert_eq!(Prefix::new_from_bytes(&bytes).unwrap(), prefix);
+    }
+});
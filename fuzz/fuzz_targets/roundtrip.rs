@@ -0,0 +1,12 @@
+#![no_main]
+
+use std::convert::TryFrom;
+
+use cid::Cid;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|cid: Cid| {
+    let bytes = cid.to_bytes();
+    assert_eq!(Cid::try_from(bytes.as_slice()).unwrap(), cid);
+    assert_eq!(Cid::try_from(cid.to_string().as_str()).unwrap(), cid);
+});
@@ -0,0 +1,116 @@
+/*
+ * C bindings of the cid crate, built with the `ffi` feature.
+ *
+ * CIDs are passed as opaque `CidHandle` pointers, created by `cid_parse` or `cid_from_bytes` and
+ * released with `cid_free`. Functions that can fail return a `CidError` code, with `CID_OK` (0)
+ * meaning success. Strings and byte buffers returned by the library are owned by the caller and
+ * must be released with `cid_string_free` and `cid_bytes_free` respectively.
+ *
+ * Keep in sync with src/ffi.rs.
+ */
+
+#ifndef CID_H
+#define CID_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Error codes returned by the functions. */
+typedef enum CidError {
+    /* Success. */
+    CID_OK = 0,
+    /* A required pointer argument was null. */
+    CID_NULL_POINTER = 1,
+    /* The input string is not valid UTF-8. */
+    CID_INVALID_UTF8 = 2,
+    /* Unknown CID codec. */
+    CID_UNKNOWN_CODEC = 3,
+    /* Input data is too short. */
+    CID_INPUT_TOO_SHORT = 4,
+    /* Multibase or multihash codec failure. */
+    CID_PARSING_ERROR = 5,
+    /* Invalid CID version. */
+    CID_INVALID_CID_VERSION = 6,
+    /* Invalid CIDv0 codec. */
+    CID_INVALID_CID_V0_CODEC = 7,
+    /* Invalid CIDv0 multihash. */
+    CID_INVALID_CID_V0_MULTIHASH = 8,
+    /* Invalid CIDv0 base encoding. */
+    CID_INVALID_CID_V0_BASE = 9,
+    /* Varint decode failure. */
+    CID_VAR_INT_DECODE_ERROR = 10,
+    /* Input data is longer than the parsing limits allow. */
+    CID_INPUT_TOO_LONG = 11,
+    /* Multihash digest is larger than the parsing limits allow. */
+    CID_DIGEST_TOO_LARGE = 12,
+    /* Any other error. */
+    CID_OTHER = 255,
+} CidError;
+
+/* An owned CID, opaque to C. */
+typedef struct CidHandle CidHandle;
+
+/*
+ * Parse a CID from a NUL-terminated string.
+ *
+ * On success the new handle is written to `out`.
+ */
+CidError cid_parse(const char *input, CidHandle **out);
+
+/*
+ * Decode a CID from `len` bytes at `data`.
+ *
+ * On success the new handle is written to `out`.
+ */
+CidError cid_from_bytes(const uint8_t *data, size_t len, CidHandle **out);
+
+/*
+ * Return the string representation of a CID, or null if `cid` is null.
+ *
+ * The string must be released with `cid_string_free`.
+ */
+char *cid_to_string(const CidHandle *cid);
+
+/*
+ * Return the encoded bytes of a CID and write their length to `len`, or null if an argument is
+ * null.
+ *
+ * The buffer must be released with `cid_bytes_free`.
+ */
+uint8_t *cid_to_bytes(const CidHandle *cid, size_t *len);
+
+/* Return the version of a CID, or UINT64_MAX if `cid` is null. */
+uint64_t cid_version(const CidHandle *cid);
+
+/* Return the multicodec code of a CID, or UINT64_MAX if `cid` is null. */
+uint64_t cid_codec(const CidHandle *cid);
+
+/* Whether two CIDs are equal. Null handles are only equal to each other. */
+bool cid_equals(const CidHandle *a, const CidHandle *b);
+
+/* Release a CID handle. Does nothing if `cid` is null. */
+void cid_free(CidHandle *cid);
+
+/* Release a string returned by `cid_to_string`. Does nothing if `string` is null. */
+void cid_string_free(char *string);
+
+/* Release a buffer returned by `cid_to_bytes`. Does nothing if `data` is null. */
+void cid_bytes_free(uint8_t *data, size_t len);
+
+/*
+ * Return a static, NUL-terminated description of an error code.
+ *
+ * Any value is safe to pass; values that aren't a `CidError` get a generic description.
+ */
+const char *cid_error_message(int err);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CID_H */
@@ -0,0 +1,12 @@
+#!/bin/sh
+# Vendor the multicodec table at an upstream revision, unmodified.
+#
+# Usage: multicodec/update.sh <revision>
+set -eu
+
+rev="${1:?usage: $0 <revision>}"
+dir="$(dirname "$0")"
+
+curl -fsSL "https://raw.githubusercontent.com/multiformats/multicodec/$rev/table.csv" \
+    -o "$dir/table.csv"
+echo "$rev" > "$dir/REVISION"
@@ -0,0 +1,53 @@
+//! actix-web integration.
+//!
+//! A [`Cid`] can be extracted directly from a route with a single dynamic path segment. Invalid
+//! CIDs are rejected with `400 Bad Request` and a message describing the problem.
+//!
+//! ```ignore
+//! #[get("/block/{cid}")]
+//! async fn block(cid: Cid) -> impl Responder { ... }
+//! ```
+//!
+//! Routes with several segments can use `web::Path<(Cid, ...)>` instead. Register [`path_config`]
+//! as app data so that invalid CIDs result in `400 Bad Request` there as well, rather than the
+//! default `404 Not Found`.
+
+use core::convert::TryFrom;
+use std::future::{ready, Ready};
+
+use actix_web::dev::Payload;
+use actix_web::http::StatusCode;
+use actix_web::web::PathConfig;
+use actix_web::{error, FromRequest, HttpRequest, ResponseError};
+
+use crate::cid::Cid;
+use crate::error::Error;
+
+impl ResponseError for Error {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+}
+
+impl FromRequest for Cid {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let mut params = req.match_info().iter();
+        let result = match (params.next(), params.next()) {
+            (Some((_, value)), None) => Cid::try_from(value)
+                .map_err(|err| error::ErrorBadRequest(format!("Invalid CID in path: {}", err))),
+            _ => Err(error::ErrorInternalServerError(
+                "Extracting a CID requires a single dynamic path segment",
+            )),
+        };
+        ready(result)
+    }
+}
+
+/// Return a [`PathConfig`] that responds with `400 Bad Request` when path deserialization fails,
+/// e.g. because of an invalid CID.
+pub fn path_config() -> PathConfig {
+    PathConfig::default().error_handler(|err, _| error::ErrorBadRequest(err))
+}
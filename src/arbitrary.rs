@@ -0,0 +1,57 @@
+//! `arbitrary::Arbitrary` implementations, for fuzzing with structurally valid CIDs.
+//!
+//! The CIDs are within the default [`Limits`], so they survive a round trip through their binary
+//! and string representations. Their digests are arbitrary bytes, not hashes of any data.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use multihash::Code;
+
+use crate::cid::Cid;
+use crate::codec::Codec;
+use crate::limits::Limits;
+use crate::version::Version;
+
+impl<'a> Arbitrary<'a> for Version {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(if u.arbitrary()? {
+            Version::V1
+        } else {
+            Version::V0
+        })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <bool as Arbitrary>::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Codec {
+    /// Mostly small codes, where the known codecs are.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let code = if u.ratio(1, 4)? {
+            u.arbitrary()?
+        } else {
+            u.int_in_range(0..=0x1ff)?
+        };
+        Ok(Codec::from_code(code))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Cid {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match Version::arbitrary(u)? {
+            Version::V0 => {
+                let digest: [u8; 32] = u.arbitrary()?;
+                let hash = multihash::wrap(Code::Sha2_256, &digest);
+                Ok(Cid::new_v0(hash).expect("SHA2-256 multihash"))
+            }
+            Version::V1 => {
+                let codec = Codec::arbitrary(u)?;
+                let code = Code::from_u64(u.arbitrary()?);
+                let len = u.int_in_range(0..=Limits::DEFAULT.max_digest_len)?;
+                let hash = multihash::wrap(code, u.bytes(len)?);
+                Ok(Cid::new_v1(codec, hash))
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Apache Arrow interop.
+//!
+//! CIDs are stored in `Binary` columns, as their encoded bytes. Such columns are marked with the
+//! [`EXTENSION_NAME`] extension type, so that readers know the bytes are CIDs.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use arrow_array::builder::{ArrayBuilder, BinaryBuilder};
+use arrow_array::BinaryArray;
+use arrow_schema::{DataType, Field};
+
+use crate::cid::Cid;
+use crate::error::Result;
+
+/// The name of the CID extension type.
+pub const EXTENSION_NAME: &str = "ipld.cid";
+
+/// Field metadata key for the name of an extension type.
+const EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+/// Field metadata key for the metadata of an extension type.
+const EXTENSION_METADATA_KEY: &str = "ARROW:extension:metadata";
+
+/// Create a field for a column of CIDs.
+pub fn cid_field(name: impl Into<String>, nullable: bool) -> Field {
+    let mut metadata = HashMap::new();
+    metadata.insert(EXTENSION_NAME_KEY.to_string(), EXTENSION_NAME.to_string());
+    metadata.insert(EXTENSION_METADATA_KEY.to_string(), String::new());
+    Field::new(name, DataType::Binary, nullable).with_metadata(metadata)
+}
+
+/// Check whether a field is a column of CIDs.
+pub fn is_cid_field(field: &Field) -> bool {
+    field.data_type() == &DataType::Binary
+        && field.metadata().get(EXTENSION_NAME_KEY).map(String::as_str) == Some(EXTENSION_NAME)
+}
+
+/// Builder for an array of CIDs.
+#[derive(Debug, Default)]
+pub struct CidArrayBuilder {
+    builder: BinaryBuilder,
+}
+
+impl CidArrayBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new builder with space for `capacity` CIDs.
+    pub fn with_capacity(capacity: usize) -> Self {
+        // Most CIDs are 36 bytes, a CIDv1 with a SHA2-256 multihash.
+        Self {
+            builder: BinaryBuilder::with_capacity(capacity, capacity * 36),
+        }
+    }
+
+    /// Append a CID.
+    pub fn append_value(&mut self, cid: &Cid) {
+        self.builder.append_value(cid.to_bytes());
+    }
+
+    /// Append a null.
+    pub fn append_null(&mut self) {
+        self.builder.append_null();
+    }
+
+    /// Append a CID or a null.
+    pub fn append_option(&mut self, cid: Option<&Cid>) {
+        match cid {
+            Some(cid) => self.append_value(cid),
+            None => self.append_null(),
+        }
+    }
+
+    /// Return the number of appended values.
+    pub fn len(&self) -> usize {
+        self.builder.len()
+    }
+
+    /// Return whether no values were appended.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Build the array and reset the builder.
+    pub fn finish(&mut self) -> BinaryArray {
+        self.builder.finish()
+    }
+}
+
+impl<'a> Extend<Option<&'a Cid>> for CidArrayBuilder {
+    fn extend<I: IntoIterator<Item = Option<&'a Cid>>>(&mut self, iter: I) {
+        for cid in iter {
+            self.append_option(cid);
+        }
+    }
+}
+
+/// Iterate over the CIDs of an array, nulls are returned as `None`.
+pub fn iter(array: &BinaryArray) -> impl Iterator<Item = Option<Result<Cid>>> + '_ {
+    array.iter().map(|bytes| bytes.map(Cid::try_from))
+}
+
+/// Read all CIDs of an array, nulls are returned as `None`.
+///
+/// Fails on the first value that isn't a valid CID.
+pub fn to_cids(array: &BinaryArray) -> Result<Vec<Option<Cid>>> {
+    iter(array).map(Option::transpose).collect()
+}
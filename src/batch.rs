@@ -0,0 +1,31 @@
+//! Hashing and parsing CIDs in parallel with rayon.
+//!
+//! The results are in the same order as the input, one per block or string, so a failure doesn't
+//! stop the rest of the batch.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use multihash::Code;
+use rayon::prelude::*;
+
+use crate::cid::Cid;
+use crate::codec::Codec;
+use crate::error::Result;
+
+/// Hash every block with the hash function of `code` and return their CIDv1, like
+/// [`Cid::hash`].
+pub fn hash_all<B: AsRef<[u8]> + Sync>(codec: Codec, code: Code, blocks: &[B]) -> Vec<Result<Cid>> {
+    blocks
+        .par_iter()
+        .map(|block| Cid::hash(codec, code, block.as_ref()))
+        .collect()
+}
+
+/// Parse every string as a CID, like `Cid::try_from(&str)`.
+pub fn parse_all<S: AsRef<str> + Sync>(strings: &[S]) -> Vec<Result<Cid>> {
+    strings
+        .par_iter()
+        .map(|s| Cid::try_from(s.as_ref()))
+        .collect()
+}
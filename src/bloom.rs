@@ -0,0 +1,192 @@
+//! A Bloom filter of CIDs, for "have I seen this block" checks without keeping the CIDs.
+//!
+//! The serialized filter is the magic bytes `CIDB`, a format version byte of 1, the number of hash
+//! functions and the number of bits as unsigned varints, then the bits as little-endian 64-bit
+//! words.
+
+use std::convert::TryInto;
+use std::f64::consts::LN_2;
+use std::io::{self, Read, Write};
+
+use multihash::Code;
+use unsigned_varint::{decode as varint_decode, encode as varint_encode};
+
+use crate::error::{Error, Result};
+use crate::key::CidKey;
+use crate::set::{digest, hash_digest};
+
+/// The magic bytes a serialized filter starts with.
+const MAGIC: &[u8; 4] = b"CIDB";
+
+/// The version of the serialization format.
+const FORMAT_VERSION: u8 = 1;
+
+/// The largest number of hash functions, which is enough for false positive rates down to 1e-9.
+const MAX_HASHES: u32 = 32;
+
+/// A Bloom filter of CIDs, keyed on their digests.
+///
+/// A filter answers whether a CID may have been inserted, with no false negatives and a false
+/// positive rate that stays below the one it was created with, as long as no more than the
+/// expected number of CIDs are inserted. The bit positions are derived from the bytes of the
+/// digest, which are already uniformly distributed for cryptographic hash functions, so nothing is
+/// hashed again. Digests that may not be, like those of identity and custom multihashes, and short
+/// digests are mixed first.
+///
+/// As the filter only looks at digests, CIDs of the same block with different versions or codecs
+/// are the same to it:
+///
+/// ```
+/// use cid::{Cid, CidBloom, Codec};
+///
+/// let v0: Cid = "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n".parse().unwrap();
+/// let mut seen = CidBloom::new(1000, 0.01);
+/// seen.insert(&v0);
+/// assert!(seen.contains(&v0));
+/// assert!(seen.contains(&Cid::new_v1(Codec::Raw, v0.hash.clone())));
+///
+/// let bytes = seen.to_bytes();
+/// assert_eq!(CidBloom::from_bytes(&bytes).unwrap(), seen);
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CidBloom {
+    bits: Vec<u64>,
+    hashes: u32,
+}
+
+impl CidBloom {
+    /// Create a filter for `expected` CIDs with a false positive rate of `fp_rate`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fp_rate` isn't between 0 and 1, exclusive.
+    pub fn new(expected: usize, fp_rate: f64) -> Self {
+        assert!(
+            fp_rate > 0.0 && fp_rate < 1.0,
+            "the false positive rate must be between 0 and 1"
+        );
+        let expected = expected.max(1) as f64;
+        let bits = (-expected * fp_rate.ln() / (LN_2 * LN_2)).ceil();
+        let words = ((bits / 64.0).ceil() as usize).max(1);
+        let hashes = (words as f64 * 64.0 / expected * LN_2).round() as u32;
+        Self {
+            bits: vec![0; words],
+            hashes: hashes.clamp(1, MAX_HASHES),
+        }
+    }
+
+    /// Return the number of bits of the filter.
+    pub fn num_bits(&self) -> u64 {
+        self.bits.len() as u64 * 64
+    }
+
+    /// Return the number of bits set for each CID.
+    pub fn num_hashes(&self) -> u32 {
+        self.hashes
+    }
+
+    /// Add a CID to the filter. Returns whether it wasn't in the filter yet, which is always
+    /// right when it returns `true`.
+    pub fn insert<K: CidKey + ?Sized>(&mut self, cid: &K) -> bool {
+        let mut added = false;
+        for bit in self.positions(cid) {
+            let (word, mask) = (bit / 64, 1 << (bit % 64));
+            added |= self.bits[word as usize] & mask == 0;
+            self.bits[word as usize] |= mask;
+        }
+        added
+    }
+
+    /// Whether the CID may be in the filter. A `false` is always right.
+    pub fn contains<K: CidKey + ?Sized>(&self, cid: &K) -> bool {
+        self.positions(cid)
+            .all(|bit| self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Remove all the CIDs from the filter.
+    pub fn clear(&mut self) {
+        self.bits.iter_mut().for_each(|word| *word = 0);
+    }
+
+    /// Serialize the filter.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(FORMAT_VERSION);
+        let mut buf = varint_encode::u32_buffer();
+        bytes.extend_from_slice(varint_encode::u32(self.hashes, &mut buf));
+        let mut buf = varint_encode::u64_buffer();
+        bytes.extend_from_slice(varint_encode::u64(self.num_bits(), &mut buf));
+        for word in &self.bits {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Deserialize a filter.
+    ///
+    /// Returns `Error::InvalidBloomFilter` if the bytes aren't a serialized filter.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let rest = bytes.strip_prefix(MAGIC).ok_or(Error::InvalidBloomFilter)?;
+        let rest = rest
+            .strip_prefix(&[FORMAT_VERSION])
+            .ok_or(Error::InvalidBloomFilter)?;
+        let (hashes, rest) = varint_decode::u32(rest).map_err(|_| Error::InvalidBloomFilter)?;
+        let (num_bits, rest) = varint_decode::u64(rest).map_err(|_| Error::InvalidBloomFilter)?;
+        if !(1..=MAX_HASHES).contains(&hashes)
+            || num_bits == 0
+            || num_bits % 64 != 0
+            || num_bits / 8 != rest.len() as u64
+        {
+            return Err(Error::InvalidBloomFilter);
+        }
+        let bits = rest
+            .chunks_exact(8)
+            .map(|word| u64::from_le_bytes(word.try_into().expect("8 bytes")))
+            .collect();
+        Ok(Self { bits, hashes })
+    }
+
+    /// Write the serialized filter to a writer.
+    pub fn write_to<W: Write>(&self, mut w: W) -> io::Result<()> {
+        w.write_all(&self.to_bytes())
+    }
+
+    /// Read a serialized filter from a reader, until its end.
+    ///
+    /// An invalid filter is reported as an `InvalidData` error, which wraps the [`Error`].
+    pub fn read_from<R: Read>(mut r: R) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        r.read_to_end(&mut bytes)?;
+        Ok(Self::from_bytes(&bytes)?)
+    }
+
+    /// Return the positions of the bits of a CID, by double hashing.
+    fn positions<K: CidKey + ?Sized>(&self, cid: &K) -> impl Iterator<Item = u64> {
+        let (_, _, hash) = cid.cid_key();
+        let digest = digest(hash);
+        let mixed = match varint_decode::u64(hash) {
+            Ok((code, _)) => matches!(Code::from_u64(code), Code::Identity | Code::Custom(_)),
+            Err(_) => true,
+        };
+        let (h1, h2) = match (digest.get(..8), digest.get(8..16)) {
+            (Some(h1), Some(h2)) if !mixed => (
+                u64::from_le_bytes(h1.try_into().expect("8 bytes")),
+                u64::from_le_bytes(h2.try_into().expect("8 bytes")),
+            ),
+            _ => {
+                let h1 = hash_digest(digest);
+                (h1, h1.rotate_left(32).wrapping_mul(0x9e37_79b9_7f4a_7c15))
+            }
+        };
+        // Both are reduced first so the sums don't wrap. The number of bits is a multiple of 64,
+        // so with an odd step the positions of up to 64 hashes are different.
+        let num_bits = self.num_bits();
+        let h1 = h1 % num_bits;
+        let h2 = (h2 % num_bits) | 1;
+        (0..self.hashes).scan(h1, move |pos, _| {
+            let bit = *pos;
+            *pos = (*pos + h2) % num_bits;
+            Some(bit)
+        })
+    }
+}
@@ -0,0 +1,25 @@
+//! CID borsh (de)serialization.
+//!
+//! A CID is (de)serialized like a `Vec<u8>` of its encoded bytes, i.e. length prefixed.
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use borsh::io::{Error, ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::cid::Cid;
+
+impl BorshSerialize for Cid {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        BorshSerialize::serialize(&self.to_bytes(), writer)
+    }
+}
+
+impl BorshDeserialize for Cid {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let bytes = Vec::<u8>::deserialize_reader(reader)?;
+        Cid::try_from(bytes).map_err(|err| Error::new(ErrorKind::InvalidData, err.to_string()))
+    }
+}
@@ -0,0 +1,69 @@
+//! BSON interop.
+//!
+//! By default a CID is stored as BSON binary (generic subtype) of its encoded bytes. Use
+//! [`to_bson`] to store it as a string instead. Both representations are accepted when
+//! converting back.
+
+use core::convert::TryFrom;
+
+use bson::spec::BinarySubtype;
+use bson::{Binary, Bson};
+
+use crate::cid::Cid;
+use crate::error::{Error, Result};
+
+/// The BSON representation of a CID.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum BsonRepr {
+    /// Binary, containing the encoded bytes.
+    #[default]
+    Binary,
+    /// String, in the base of the CID.
+    String,
+}
+
+/// Convert a CID to BSON, with the given representation.
+pub fn to_bson(cid: &Cid, repr: BsonRepr) -> Bson {
+    match repr {
+        BsonRepr::Binary => Bson::Binary(Binary {
+            subtype: BinarySubtype::Generic,
+            bytes: cid.to_bytes(),
+        }),
+        BsonRepr::String => Bson::String(cid.to_string()),
+    }
+}
+
+impl From<Cid> for Bson {
+    fn from(cid: Cid) -> Self {
+        to_bson(&cid, BsonRepr::default())
+    }
+}
+
+impl From<&Cid> for Bson {
+    fn from(cid: &Cid) -> Self {
+        to_bson(cid, BsonRepr::default())
+    }
+}
+
+impl TryFrom<Bson> for Cid {
+    type Error = Error;
+
+    fn try_from(bson: Bson) -> Result<Self> {
+        Cid::try_from(&bson)
+    }
+}
+
+impl TryFrom<&Bson> for Cid {
+    type Error = Error;
+
+    fn try_from(bson: &Bson) -> Result<Self> {
+        match bson {
+            Bson::Binary(Binary {
+                subtype: BinarySubtype::Generic,
+                bytes,
+            }) => Cid::try_from(bytes.as_slice()),
+            Bson::String(string) => Cid::try_from(string.as_str()),
+            _ => Err(Error::InvalidBson),
+        }
+    }
+}
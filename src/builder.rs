@@ -0,0 +1,120 @@
+use multihash::Code;
+
+use crate::cid::Cid;
+use crate::codec::Codec;
+use crate::error::{Error, Result};
+use crate::prefix::Prefix;
+use crate::version::Version;
+
+/// Builder for CIDs of hashed data.
+///
+/// By default it builds CIDv1 with the Raw codec and a SHA2-256 multihash:
+///
+/// ```
+/// use cid::{CidBuilder, Codec, Version};
+/// use multihash::Code;
+///
+/// let cid = CidBuilder::new()
+///     .version(Version::V1)
+///     .codec(Codec::DagCBOR)
+///     .hash_with(Code::Blake2b256)
+///     .build(b"data")
+///     .unwrap();
+/// assert_eq!(cid.codec, Codec::DagCBOR);
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct CidBuilder {
+    version: Version,
+    codec: Codec,
+    code: Code,
+    len: Option<usize>,
+}
+
+impl Default for CidBuilder {
+    fn default() -> Self {
+        CidBuilder {
+            version: Version::V1,
+            codec: Codec::Raw,
+            code: Code::Sha2_256,
+            len: None,
+        }
+    }
+}
+
+impl CidBuilder {
+    /// Create a builder with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the version of the CID.
+    ///
+    /// Setting CIDv0 also sets the DagProtobuf codec and SHA2-256 multihash it requires.
+    pub fn version(mut self, version: Version) -> Self {
+        self.version = version;
+        if version == Version::V0 {
+            self.codec = Codec::DagProtobuf;
+            self.code = Code::Sha2_256;
+        }
+        self
+    }
+
+    /// Set the codec of the CID.
+    pub fn codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Set the hash function of the multihash.
+    pub fn hash_with(mut self, code: Code) -> Self {
+        self.code = code;
+        self
+    }
+
+    /// Truncate the digest to `len` bytes. Lengths larger than the digest have no effect.
+    pub fn hash_len(mut self, len: usize) -> Self {
+        self.len = Some(len);
+        self
+    }
+
+    /// Check that the settings result in a valid CID.
+    pub fn validate(&self) -> Result<()> {
+        if self.version == Version::V0 {
+            if self.codec != Codec::DagProtobuf {
+                return Err(Error::InvalidCidV0Codec);
+            }
+            if self.code != Code::Sha2_256 || self.len.is_some_and(|len| len < 32) {
+                return Err(Error::InvalidCidV0Multihash);
+            }
+        }
+        if let Code::Custom(_) = self.code {
+            return Err(Error::UnsupportedMultihash);
+        }
+        Ok(())
+    }
+
+    /// Hash `data` and return its CID.
+    pub fn build(&self, data: &[u8]) -> Result<Cid> {
+        self.validate()?;
+        let mut hash = self
+            .code
+            .hasher()
+            .ok_or(Error::UnsupportedMultihash)?
+            .digest(data);
+        if let Some(len) = self.len.filter(|len| *len < hash.digest().len()) {
+            hash = multihash::wrap(self.code, &hash.digest()[..len]);
+        }
+        Cid::new(self.version, self.codec, hash)
+    }
+}
+
+impl From<&Prefix> for CidBuilder {
+    fn from(prefix: &Prefix) -> Self {
+        CidBuilder {
+            version: prefix.version,
+            codec: prefix.codec,
+            code: prefix.mh_type,
+            len: Some(prefix.mh_len),
+        }
+    }
+}
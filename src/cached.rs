@@ -0,0 +1,125 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use std::sync::OnceLock;
+
+use crate::cid::Cid;
+
+/// A [`Cid`] that caches its encoded bytes and string representation.
+///
+/// Both are computed on first use only, and then reused by every later call. The wrapped CID can
+/// be accessed through `Deref`, but not modified, so the cache is always up to date.
+#[derive(Clone)]
+pub struct CachedCid {
+    cid: Cid,
+    bytes: OnceLock<Vec<u8>>,
+    string: OnceLock<String>,
+}
+
+impl CachedCid {
+    /// Wrap a CID, with an empty cache.
+    pub fn new(cid: Cid) -> Self {
+        CachedCid {
+            cid,
+            bytes: OnceLock::new(),
+            string: OnceLock::new(),
+        }
+    }
+
+    /// Return the wrapped CID.
+    pub fn cid(&self) -> &Cid {
+        &self.cid
+    }
+
+    /// Unwrap the CID, dropping the cache.
+    pub fn into_inner(self) -> Cid {
+        self.cid
+    }
+
+    /// The encoded bytes of the CID.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.get_or_init(|| self.cid.to_bytes())
+    }
+
+    /// The string representation of the CID.
+    pub fn as_str(&self) -> &str {
+        self.string.get_or_init(|| self.cid.to_string())
+    }
+}
+
+impl Deref for CachedCid {
+    type Target = Cid;
+
+    fn deref(&self) -> &Cid {
+        &self.cid
+    }
+}
+
+impl AsRef<Cid> for CachedCid {
+    fn as_ref(&self) -> &Cid {
+        &self.cid
+    }
+}
+
+impl From<Cid> for CachedCid {
+    fn from(cid: Cid) -> Self {
+        CachedCid::new(cid)
+    }
+}
+
+impl From<CachedCid> for Cid {
+    fn from(cid: CachedCid) -> Self {
+        cid.into_inner()
+    }
+}
+
+impl fmt::Debug for CachedCid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CachedCid").field(&self.cid).finish()
+    }
+}
+
+impl fmt::Display for CachedCid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl PartialEq for CachedCid {
+    fn eq(&self, other: &Self) -> bool {
+        self.cid == other.cid
+    }
+}
+
+impl Eq for CachedCid {}
+
+impl PartialEq<Cid> for CachedCid {
+    fn eq(&self, other: &Cid) -> bool {
+        self.cid == *other
+    }
+}
+
+impl PartialEq<CachedCid> for Cid {
+    fn eq(&self, other: &CachedCid) -> bool {
+        *self == other.cid
+    }
+}
+
+impl PartialOrd for CachedCid {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CachedCid {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cid.cmp(&other.cid)
+    }
+}
+
+impl Hash for CachedCid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cid.hash(state);
+    }
+}
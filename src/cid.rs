@@ -1,16 +1,48 @@
-use std::convert::TryFrom;
+use alloc::borrow::Cow;
+#[cfg(feature = "hasher")]
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt::Write;
 
 use multibase::Base;
-use multihash::{Code, Multihash, MultihashRef};
+use multihash::{Code, Multihash};
 use unsigned_varint::{decode as varint_decode, encode as varint_encode};
 
+use crate::builder::CidBuilder;
+use crate::cid_ref::CidRef;
 use crate::codec::Codec;
-use crate::error::{Error, Result};
+use crate::encoding;
+#[cfg(feature = "hasher")]
+use crate::error::Mismatch;
+use crate::error::{Cause, Component, Error, Result};
+#[cfg(feature = "std")]
+use crate::framing::{CidReader, Step};
+#[cfg(feature = "hasher")]
+use crate::hasher::Hasher;
+use crate::limits::Limits;
 use crate::prefix::Prefix;
 use crate::version::Version;
 
 /// Representation of a CID.
-#[derive(PartialEq, Eq, Clone, Debug, PartialOrd, Ord)]
+///
+/// Equality, ordering and hashing only take the version, codec and multihash into account, the
+/// multibase a CID was parsed from is ignored. CIDs are ordered by version and codec number, then
+/// by the bytes of the multihash, so the order is the same on every machine.
+///
+/// Multihashes of up to 38 bytes, which includes any 256-bit digest, are stored inline. Larger
+/// ones, like those of 512-bit digests, are allocated once when the CID is created and reference
+/// counted, so cloning a `Cid` never allocates. The inline capacity is fixed by the `Multihash`
+/// type of the public `hash` field; [`CidN<64>`](crate::CidN) stores 512-bit digests inline.
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::AsExpression, diesel::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Binary),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
 pub struct Cid {
     /// The version of CID.
     pub version: Version,
@@ -18,9 +50,18 @@ pub struct Cid {
     pub codec: Codec,
     /// The multihash of CID.
     pub hash: Multihash,
+    /// The multibase the CID was parsed from, if any.
+    pub(crate) base: Option<Base>,
 }
 
+/// The size of the buffer data is read into by [`Cid::verify_reader`].
+#[cfg(all(feature = "std", feature = "hasher"))]
+const DEFAULT_BUF_SIZE: usize = 8 * 1024;
+
 impl Cid {
+    /// The default maximum length of the data embedded by [`Cid::new_inline`].
+    pub const MAX_INLINE_LEN: usize = 32;
+
     /// Create a new CIDv0.
     pub fn new_v0(hash: Multihash) -> Result<Cid> {
         if hash.algorithm() != Code::Sha2_256 {
@@ -30,36 +71,286 @@ impl Cid {
             version: Version::V0,
             codec: Codec::DagProtobuf,
             hash,
+            base: None,
         })
     }
 
+    /// Create a new CIDv0, without checking that the multihash is SHA2-256.
+    ///
+    /// Only use this for multihashes that are known to be valid for CIDv0, e.g. from a trusted
+    /// index. Otherwise the CID can't be decoded again.
+    pub fn new_v0_unchecked(hash: Multihash) -> Cid {
+        Cid {
+            version: Version::V0,
+            codec: Codec::DagProtobuf,
+            hash,
+            base: None,
+        }
+    }
+
+    /// Decode a CID from encoded bytes that are known to be valid, e.g. from a trusted index.
+    ///
+    /// Unlike `Cid::try_from`, this skips the checks that the multihash is consistent (the digest
+    /// is taken as is, without checking its length) and that a CIDv0 uses the DagProtobuf codec
+    /// and a SHA2-256 multihash.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the version, codec or multihash header can't be decoded.
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Cid {
+        if Version::is_v0_binary(bytes) {
+            return Self::new_v0_unchecked(wrap_unchecked(bytes));
+        }
+        let (version, rest) = varint_decode::u64(bytes).expect("invalid CID version");
+        let (codec, rest) = varint_decode::u64(rest).expect("invalid CID codec");
+        Cid {
+            version: Version::from(version).expect("invalid CID version"),
+            codec: Codec::from_code(codec),
+            hash: wrap_unchecked(rest),
+            base: None,
+        }
+    }
+
     /// Create a new CIDv1.
     pub fn new_v1(codec: Codec, hash: Multihash) -> Cid {
         Cid {
             version: Version::V1,
             codec,
             hash,
+            base: None,
         }
     }
 
+    /// Create a new CIDv1 from an encoded multihash that is known to be valid, e.g. from a
+    /// trusted index.
+    ///
+    /// Unlike decoding the multihash with `Multihash::from_bytes`, this doesn't check that the
+    /// length of the digest matches the multihash header.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the multihash header can't be decoded.
+    pub fn new_v1_unchecked(codec: Codec, hash: &[u8]) -> Cid {
+        Self::new_v1(codec, wrap_unchecked(hash))
+    }
+
     /// Create a new CID.
-    pub fn new(codec: Codec, hash: Multihash) -> Result<Cid> {
-        match codec {
-            Codec::DagProtobuf => Self::new_v0(hash),
-            _ => Ok(Self::new_v1(codec, hash)),
+    pub fn new(version: Version, codec: Codec, hash: Multihash) -> Result<Cid> {
+        match version {
+            Version::V0 => {
+                if codec != Codec::DagProtobuf {
+                    return Err(Error::InvalidCidV0Codec);
+                }
+                Self::new_v0(hash)
+            }
+            Version::V1 => Ok(Self::new_v1(codec, hash)),
+        }
+    }
+
+    /// Create a new CIDv1 that embeds `data` in an identity multihash.
+    ///
+    /// Returns `Error::InlineDataTooLarge` if `data` is longer than [`Cid::MAX_INLINE_LEN`].
+    pub fn new_inline(codec: Codec, data: &[u8]) -> Result<Cid> {
+        Self::new_inline_with_max_len(codec, data, Self::MAX_INLINE_LEN)
+    }
+
+    /// Create a new CIDv1 that embeds `data` in an identity multihash, if it is at most `max_len`
+    /// bytes long.
+    pub fn new_inline_with_max_len(codec: Codec, data: &[u8], max_len: usize) -> Result<Cid> {
+        if data.len() > max_len {
+            return Err(Error::InlineDataTooLarge);
         }
+        Ok(Self::new_v1(codec, multihash::wrap(Code::Identity, data)))
+    }
+
+    /// Hash `data` with the hash function of `code` and return its CIDv1.
+    ///
+    /// Returns `Error::UnsupportedMultihash` for custom multihash codes. See [`CidBuilder`] for
+    /// CIDv0 and truncated digests.
+    pub fn hash(codec: Codec, code: Code, data: &[u8]) -> Result<Cid> {
+        CidBuilder::new().codec(codec).hash_with(code).build(data)
+    }
+
+    /// Hash the data read from `r` with the hash function of `code` and return its CIDv1.
+    ///
+    /// The data is read in chunks of `buf_size` bytes and hashed as it is read, so it is never
+    /// loaded into memory as a whole. Identity multihashes are limited to
+    /// [`Cid::MAX_INLINE_LEN`] bytes of data. Invalid parameters are reported as `InvalidData`
+    /// errors, which wrap the [`Error`].
+    #[cfg(all(feature = "std", feature = "hasher"))]
+    pub fn from_reader<R: std::io::Read>(
+        codec: Codec,
+        code: Code,
+        r: R,
+        buf_size: usize,
+    ) -> std::io::Result<Cid> {
+        let mut hasher = Hasher::new(code)?;
+        if code == Code::Identity {
+            hasher.update_reader(r.take(Self::MAX_INLINE_LEN as u64 + 1), buf_size)?;
+            let hash = hasher.finalize();
+            return Ok(Self::new_inline(codec, hash.digest())?);
+        }
+        hasher.update_reader(r, buf_size)?;
+        Ok(Self::new_v1(codec, hasher.finalize()))
+    }
+
+    /// Create a new CID from the parts returned by [`Cid::into_parts`].
+    ///
+    /// This is the same as [`Cid::new`].
+    pub fn from_parts(version: Version, codec: Codec, hash: Multihash) -> Result<Cid> {
+        Self::new(version, codec, hash)
+    }
+
+    /// Split the CID into its version, codec and multihash, without cloning the multihash.
+    pub fn into_parts(self) -> (Version, Codec, Multihash) {
+        (self.version, self.codec, self.hash)
+    }
+
+    /// Parse a CID from its encoded bytes, checked against the given limits.
+    ///
+    /// `TryFrom<&[u8]>` uses [`Limits::DEFAULT`].
+    pub fn from_bytes_with_limits(bytes: &[u8], limits: &Limits) -> Result<Cid> {
+        Ok(CidRef::from_bytes_with_limits(bytes, limits)?.to_owned())
+    }
+
+    /// Parse a CID from its string representation, checked against the given limits.
+    ///
+    /// `TryFrom<&str>` and `FromStr` use [`Limits::DEFAULT`].
+    pub fn from_str_with_limits(cid_str: &str, limits: &Limits) -> Result<Cid> {
+        let (base, decoded) = decode_str_with_limits(cid_str, limits)?;
+        let mut cid = Self::from_bytes_with_limits(&decoded, limits)?;
+        cid.base = Some(base);
+        Ok(cid)
+    }
+
+    /// Parse a CID from its encoded bytes, rejecting anything but the canonical encoding.
+    ///
+    /// See [`CidRef::from_bytes_strict`] for the additional checks.
+    pub fn from_bytes_strict(bytes: &[u8]) -> Result<Cid> {
+        Ok(CidRef::from_bytes_strict(bytes)?.to_owned())
+    }
+
+    /// Parse a CID from its string representation, rejecting anything but the canonical encoding.
+    ///
+    /// On top of the checks of [`Cid::from_bytes_strict`], this rejects `/ipfs/` paths, padded
+    /// multibase encodings (`Error::PaddedMultibase`), payloads in the wrong case and CIDv0 that
+    /// isn't encoded as 46 base58btc characters starting with "Qm" (`Error::InvalidCidV0String`).
+    pub fn from_str_strict(cid_str: &str) -> Result<Cid> {
+        if cid_str.len() < 2 {
+            return Err(Error::InputTooShort);
+        }
+        if cid_str.len() > Limits::DEFAULT.max_str_len() {
+            return Err(Error::InputTooLong);
+        }
+
+        if cid_str.starts_with("Qm") {
+            if !Version::is_v0_str(cid_str) {
+                return Err(Error::InvalidCidV0String);
+            }
+            let decoded = Base::Base58Btc
+                .decode(cid_str)
+                .map_err(|err| multibase_error(err, Base::Base58Btc, cid_str, 0))?;
+            let mut cid = Self::from_bytes_strict(&decoded)?;
+            if cid.version != Version::V0 {
+                return Err(Error::InvalidCidV0String);
+            }
+            cid.base = Some(Base::Base58Btc);
+            return Ok(cid);
+        }
+
+        let code = cid_str.chars().next().ok_or(Error::InputTooShort)?;
+        let base = Base::from_code(code).map_err(|err| Error::Malformed {
+            component: Component::Multibase,
+            offset: 0,
+            source: Some(Cause::Multibase(err)),
+        })?;
+        if is_padded(base) || cid_str.ends_with('=') {
+            return Err(Error::PaddedMultibase);
+        }
+
+        let data = &cid_str[code.len_utf8()..];
+        let decoded = base
+            .decode(data)
+            .map_err(|err| multibase_error(err, base, data, 1))?;
+        let mut cid = Self::from_bytes_strict(&decoded)?;
+        if cid.version == Version::V0 {
+            return Err(Error::InvalidCidV0String);
+        }
+        cid.base = Some(base);
+        Ok(cid)
     }
 
     /// Create a new CID from a prefix and some data.
-    pub fn new_from_prefix(prefix: &Prefix, data: &[u8]) -> Cid {
-        let mut hash = prefix.mh_type.hasher().unwrap().digest(data);
+    ///
+    /// Returns `Error::UnsupportedMultihash` if the multihash type of the prefix is a
+    /// `Code::Custom`, as there is no hash function for it.
+    pub fn new_from_prefix(prefix: &Prefix, data: &[u8]) -> Result<Cid> {
+        let mut hash = prefix
+            .mh_type
+            .hasher()
+            .ok_or(Error::UnsupportedMultihash)?
+            .digest(data);
         if prefix.mh_len < hash.digest().len() {
             hash = multihash::wrap(hash.algorithm(), &hash.digest()[..prefix.mh_len]);
         }
-        Cid {
+        Ok(Cid {
             version: prefix.version,
             codec: prefix.codec,
             hash,
+            base: None,
+        })
+    }
+
+    /// Convert to CIDv1, with the same codec and multihash. A CIDv1 is returned unchanged.
+    pub fn into_v1(self) -> Cid {
+        match self.version {
+            Version::V0 => Cid {
+                version: Version::V1,
+                // base58btc was implied by CIDv0, use the default base of CIDv1.
+                base: None,
+                ..self
+            },
+            Version::V1 => self,
+        }
+    }
+
+    /// Convert to CIDv0, which requires the DagProtobuf codec and a SHA2-256 multihash. A CIDv0
+    /// is returned unchanged.
+    pub fn try_into_v0(self) -> Result<Cid> {
+        match self.version {
+            Version::V0 => Ok(self),
+            Version::V1 => Self::new(Version::V0, self.codec, self.hash),
+        }
+    }
+
+    /// Create a libp2p-key CIDv1 from the multihash of a PeerId.
+    ///
+    /// The multihash must be an identity or SHA2-256 multihash of a public key, as in a PeerId.
+    pub fn from_peer_key_multihash(hash: Multihash) -> Result<Cid> {
+        match hash.algorithm() {
+            Code::Identity | Code::Sha2_256 => Ok(Self::new_v1(Codec::Libp2pKey, hash)),
+            _ => Err(Error::InvalidPeerId),
+        }
+    }
+
+    /// Return the multihash of the PeerId, if this is a libp2p-key CID.
+    pub fn try_into_peer_multihash(self) -> Result<Multihash> {
+        match (self.codec, self.hash.algorithm()) {
+            (Codec::Libp2pKey, Code::Identity) | (Codec::Libp2pKey, Code::Sha2_256) => {
+                Ok(self.hash)
+            }
+            _ => Err(Error::InvalidPeerId),
+        }
+    }
+
+    /// Return the multibase used for the string representation of the CID.
+    ///
+    /// This is the base the CID was parsed from, so that it is displayed the same way again.
+    /// Otherwise it is base58btc for CIDv0 and base32 lower for CIDv1.
+    pub fn base(&self) -> Base {
+        match self.version {
+            Version::V0 => Base::Base58Btc,
+            Version::V1 => self.base.unwrap_or(Base::Base32Lower),
         }
     }
 
@@ -67,8 +358,56 @@ impl Cid {
         Base::Base58Btc.encode(self.hash.as_bytes())
     }
 
-    fn to_string_v1(&self) -> String {
-        multibase::encode(Base::Base32Lower, self.to_bytes().as_slice())
+    /// Convert CID to a multibase encoded string.
+    ///
+    /// A CIDv0 can only be represented as base58btc, any other base returns an error.
+    pub fn to_string_of_base(&self, base: Base) -> Result<String> {
+        match self.version {
+            Version::V0 => {
+                if base == Base::Base58Btc {
+                    Ok(self.to_string_v0())
+                } else {
+                    Err(Error::InvalidCidV0Base)
+                }
+            }
+            Version::V1 => Ok(multibase::encode(base, self.to_bytes().as_slice())),
+        }
+    }
+
+    /// Return a short form of the string representation for logs and UIs, like `bafybei…vyku`.
+    ///
+    /// The multibase prefix and the start of the string are kept, so the kind of CID is still
+    /// recognizable, with the end of the string to tell CIDs apart. It can't be parsed back.
+    pub fn short(&self) -> String {
+        const HEAD: usize = 7;
+        const TAIL: usize = 4;
+
+        let full = self.to_string();
+        // Count characters rather than bytes, in case the multibase isn't ASCII.
+        let chars = full.chars().count();
+        if chars <= HEAD + TAIL + 1 {
+            return full;
+        }
+        let head: String = full.chars().take(HEAD).collect();
+        let tail: String = full.chars().skip(chars - TAIL).collect();
+        format!("{}…{}", head, tail)
+    }
+
+    /// Return a file name for the CID that is safe on case-insensitive filesystems.
+    ///
+    /// The CID is converted to CIDv1 and encoded in base32 lower, without padding, as base58btc
+    /// names can collide when their case is folded.
+    pub fn to_safe_filename(&self) -> String {
+        multibase::encode(Base::Base32Lower, self.clone().into_v1().to_bytes())
+    }
+
+    /// Parse a file name written by [`Cid::to_safe_filename`], or any other CID string.
+    ///
+    /// Everything from the first `.` on is ignored, so extensions like `.block` or `.data` are
+    /// allowed.
+    pub fn from_safe_filename(name: &str) -> Result<Cid> {
+        let stem = name.split('.').next().unwrap_or_default();
+        Cid::try_from(stem)
     }
 
     fn to_bytes_v0(&self) -> Vec<u8> {
@@ -76,7 +415,7 @@ impl Cid {
     }
 
     fn to_bytes_v1(&self) -> Vec<u8> {
-        let mut res = Vec::with_capacity(16);
+        let mut res = Vec::with_capacity(self.encoded_len());
 
         let mut buf = varint_encode::u64_buffer();
         let version = varint_encode::u64(self.version.into(), &mut buf);
@@ -97,6 +436,129 @@ impl Cid {
         }
     }
 
+    /// Return the length of the encoded bytes of the CID.
+    pub fn encoded_len(&self) -> usize {
+        match self.version {
+            Version::V0 => self.hash.len(),
+            Version::V1 => {
+                let mut buf = varint_encode::u64_buffer();
+                let version = varint_encode::u64(self.version.into(), &mut buf).len();
+                let mut buf = varint_encode::u64_buffer();
+                let codec = varint_encode::u64(self.codec.into(), &mut buf).len();
+                version + codec + self.hash.len()
+            }
+        }
+    }
+
+    /// Write the encoded bytes of the CID to a writer, without allocating.
+    ///
+    /// Returns the number of bytes written, which is always `encoded_len()`.
+    #[cfg(feature = "std")]
+    pub fn write_bytes<W: std::io::Write>(&self, mut w: W) -> std::io::Result<usize> {
+        if self.version == Version::V1 {
+            let mut buf = varint_encode::u64_buffer();
+            w.write_all(varint_encode::u64(self.version.into(), &mut buf))?;
+            let mut buf = varint_encode::u64_buffer();
+            w.write_all(varint_encode::u64(self.codec.into(), &mut buf))?;
+        }
+        w.write_all(&self.hash)?;
+        Ok(self.encoded_len())
+    }
+
+    /// Decode a CID from the start of a buffer, which may contain more data after it.
+    ///
+    /// Returns the CID and the number of bytes it takes up, so that parsing can continue after
+    /// it. See [`CidRef::decode`] to decode without copying the multihash.
+    pub fn decode(bytes: &[u8]) -> Result<(Cid, usize)> {
+        let (cid, len) = CidRef::decode(bytes)?;
+        Ok((cid.to_owned(), len))
+    }
+
+    /// Read exactly one encoded CID from a reader.
+    ///
+    /// Only the bytes of the CID are consumed, so the reader can be positioned right after it, as
+    /// in CAR files. The CID is checked against [`Limits::DEFAULT`]; invalid CIDs are reported as
+    /// `InvalidData` errors, which wrap the [`Error`].
+    #[cfg(feature = "std")]
+    pub fn read_bytes<R: std::io::Read>(mut r: R) -> std::io::Result<Cid> {
+        let mut reader = CidReader::new();
+        loop {
+            match reader.step() {
+                Step::Byte => {
+                    let mut byte = [0];
+                    r.read_exact(&mut byte)?;
+                    reader.push(byte[0])?;
+                }
+                Step::Digest => {
+                    r.read_exact(reader.digest_mut())?;
+                    return Ok(reader.finish()?);
+                }
+            }
+        }
+    }
+
+    /// Return the digest of the multihash, without its code and length.
+    pub fn digest(&self) -> &[u8] {
+        self.hash.digest()
+    }
+
+    /// Check that `data` is the content the CID refers to, by hashing it with the hash function of
+    /// the multihash.
+    ///
+    /// Returns `Error::DigestMismatch` with both digests if they differ, and
+    /// `Error::UnsupportedMultihash` if there is no hash function for the multihash code.
+    /// Truncated digests are compared with the same length.
+    #[cfg(feature = "hasher")]
+    pub fn verify(&self, data: &[u8]) -> Result<()> {
+        let mut hasher = Hasher::new(self.hash.algorithm())?;
+        hasher.update(data);
+        self.check_hash(hasher.finalize())
+    }
+
+    /// Check that the content read from `r` is the content the CID refers to, like
+    /// [`Cid::verify`], without buffering it.
+    ///
+    /// The data is hashed in chunks as it is read. Mismatches are reported as `InvalidData`
+    /// errors, which wrap the [`Error`].
+    #[cfg(all(feature = "std", feature = "hasher"))]
+    pub fn verify_reader<R: std::io::Read>(&self, r: R) -> std::io::Result<()> {
+        let mut hasher = Hasher::new(self.hash.algorithm())?;
+        // Identity multihashes buffer the data, so read one byte more than can match.
+        let limit = match self.hash.algorithm() {
+            Code::Identity => self.digest().len() as u64 + 1,
+            _ => u64::MAX,
+        };
+        hasher.update_reader(r.take(limit), DEFAULT_BUF_SIZE)?;
+        Ok(self.check_hash(hasher.finalize())?)
+    }
+
+    /// Compare the multihash of the data with the one of the CID.
+    #[cfg(feature = "hasher")]
+    fn check_hash(&self, hash: Multihash) -> Result<()> {
+        let len = self.digest().len();
+        // Identity "digests" can't be truncated.
+        let actual = match hash.algorithm() {
+            Code::Identity => hash,
+            code if hash.digest().len() > len => multihash::wrap(code, &hash.digest()[..len]),
+            _ => hash,
+        };
+        if actual == self.hash {
+            return Ok(());
+        }
+        Err(Error::DigestMismatch(Box::new(Mismatch {
+            expected: self.hash.clone(),
+            actual,
+        })))
+    }
+
+    /// Return the data embedded in an identity multihash, or `None` for any other multihash.
+    pub fn inline_data(&self) -> Option<&[u8]> {
+        match self.hash.algorithm() {
+            Code::Identity => Some(self.hash.digest()),
+            _ => None,
+        }
+    }
+
     /// Return the prefix of the CID.
     pub fn prefix(&self) -> Prefix {
         Prefix {
@@ -106,26 +568,223 @@ impl Cid {
             mh_len: self.hash.digest().len(),
         }
     }
+
+    /// Write the string representation of the CID.
+    fn write_multibase<W: Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        self.write_multibase_of(w, self.base())
+    }
+
+    /// Write the CID in base58btc for CIDv0 and base32 lower for CIDv1, whatever base it was
+    /// parsed from.
+    #[cfg(feature = "serde")]
+    pub(crate) fn write_canonical<W: Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        self.write_multibase_of(w, Base::Base32Lower)
+    }
+
+    /// Write the CID in the multibase `base`, which is ignored for CIDv0.
+    fn write_multibase_of<W: Write + ?Sized>(&self, w: &mut W, base: Base) -> core::fmt::Result {
+        match self.version {
+            Version::V0 => self.write_encoded(w, Base::Base58Btc),
+            Version::V1 => {
+                w.write_char(base.code())?;
+                self.write_encoded(w, base)
+            }
+        }
+    }
+
+    /// Write the binary representation of the CID in the given base, without multibase prefix.
+    fn write_encoded<W: Write + ?Sized>(&self, w: &mut W, base: Base) -> core::fmt::Result {
+        match self.version {
+            Version::V0 => encoding::write_base(w, base, &[self.hash.as_bytes()]),
+            Version::V1 => {
+                let mut buf = varint_encode::u64_buffer();
+                let version = varint_encode::u64(self.version.into(), &mut buf);
+                let mut buf = varint_encode::u64_buffer();
+                let codec = varint_encode::u64(self.codec.into(), &mut buf);
+                encoding::write_base(w, base, &[version, codec, self.hash.as_bytes()])
+            }
+        }
+    }
+
+    /// Write the parts of the CID, one per line, for the alternate `Display` form.
+    fn write_breakdown<W: Write>(&self, w: &mut W) -> core::fmt::Result {
+        let code = self.hash.algorithm();
+        write!(w, "\n  version: {}", u64::from(self.version))?;
+        match self.codec.name() {
+            Some(name) => write!(w, "\n  codec: {} ({:#x})", name, u64::from(self.codec))?,
+            None => write!(w, "\n  codec: {:#x}", u64::from(self.codec))?,
+        }
+        match multihash_name(code) {
+            Some(name) => write!(w, "\n  multihash: {} ({:#x})", name, code.to_u64())?,
+            None => write!(w, "\n  multihash: {:#x}", code.to_u64())?,
+        }
+        write!(
+            w,
+            "\n  digest length: {} bytes\n  digest: ",
+            self.digest().len()
+        )?;
+        self.digest()
+            .iter()
+            .try_for_each(|byte| write!(w, "{:02x}", byte))
+    }
+
+    /// The key CIDs are ordered by.
+    pub(crate) fn order_key(&self) -> (u64, u64, &[u8]) {
+        (self.version.into(), self.codec.into(), self.hash.as_bytes())
+    }
+
+    /// Whether the string is the CID in the multibase of its prefix, or in base58btc without a
+    /// prefix for CIDv0.
+    fn eq_str(&self, cid_str: &str) -> bool {
+        match self.version {
+            Version::V0 => encoding::matches(cid_str, |w| self.write_encoded(w, Base::Base58Btc)),
+            Version::V1 => {
+                let mut chars = cid_str.chars();
+                match chars.next().map(Base::from_code) {
+                    Some(Ok(base)) => {
+                        encoding::matches(chars.as_str(), |w| self.write_encoded(w, base))
+                    }
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    /// Whether the bytes are the binary representation of the CID.
+    fn eq_bytes(&self, bytes: &[u8]) -> bool {
+        match self.version {
+            Version::V0 => self.hash.as_bytes() == bytes,
+            Version::V1 => {
+                let mut buf = varint_encode::u64_buffer();
+                let version = varint_encode::u64(self.version.into(), &mut buf);
+                let mut buf = varint_encode::u64_buffer();
+                let codec = varint_encode::u64(self.codec.into(), &mut buf);
+                bytes
+                    .strip_prefix(version)
+                    .and_then(|rest| rest.strip_prefix(codec))
+                    == Some(self.hash.as_bytes())
+            }
+        }
+    }
 }
 
-#[allow(clippy::derive_hash_xor_eq)]
-impl std::hash::Hash for Cid {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.to_bytes().hash(state);
+impl PartialEq for Cid {
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version && self.codec == other.codec && self.hash == other.hash
     }
 }
 
-impl std::fmt::Display for Cid {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let output = match self.version {
-            Version::V0 => self.to_string_v0(),
-            Version::V1 => self.to_string_v1(),
+impl Eq for Cid {}
+
+// Comparisons with the string and binary representations, in both directions.
+macro_rules! impl_eq {
+    ($($other:ty => $eq:ident,)*) => {
+        $(
+            impl PartialEq<$other> for Cid {
+                /// Compares with the string or binary representation, without allocating.
+                ///
+                /// A string is equal if it is the CID encoded in the multibase of its prefix, or
+                /// in base58btc without a prefix for CIDv0. The same CID in another base or
+                /// version is not equal.
+                fn eq(&self, other: &$other) -> bool {
+                    self.$eq(other)
+                }
+            }
+
+            impl PartialEq<Cid> for $other {
+                fn eq(&self, other: &Cid) -> bool {
+                    other.$eq(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_eq! {
+    str => eq_str,
+    &str => eq_str,
+    [u8] => eq_bytes,
+    &[u8] => eq_bytes,
+}
+
+impl PartialOrd for Cid {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cid {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.order_key().cmp(&other.order_key())
+    }
+}
+
+impl core::hash::Hash for Cid {
+    /// Hashes the version, codec and the whole multihash, without allocating. The same as for
+    /// [`CidRef`].
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.order_key().hash(state);
+    }
+}
+
+impl core::fmt::Debug for Cid {
+    /// Writes the string representation and the names of the codec and hash function, like
+    /// `Cid("bafy...", dag-pb, sha2-256)`.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let code = self.hash.algorithm();
+        let mut tuple = f.debug_tuple("Cid");
+        tuple
+            .field(&format_args!("\"{}\"", self))
+            .field(&format_args!("{}", self.codec));
+        match multihash_name(code) {
+            Some(name) => tuple.field(&format_args!("{}", name)),
+            None => tuple.field(&format_args!("{:#x}", code.to_u64())),
         };
-        write!(f, "{}", output)
+        tuple.finish()
+    }
+}
+
+impl core::fmt::Display for Cid {
+    /// Writes the CID in its multibase, without allocating.
+    ///
+    /// The width, fill and alignment of the formatter are respected. The alternate form (`{:#}`)
+    /// is followed by the version, codec, multihash code and digest, one per line, for debugging.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if f.alternate() {
+            self.write_multibase(f)?;
+            return self.write_breakdown(f);
+        }
+        encoding::pad(f, |w| self.write_multibase(w))
     }
 }
 
-impl std::str::FromStr for Cid {
+impl core::fmt::LowerHex for Cid {
+    /// Writes the binary representation of the CID in lowercase hex, without allocating.
+    ///
+    /// The alternate form (`{:#x}`) is prefixed with `0x`.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let prefix = if f.alternate() { "0x" } else { "" };
+        encoding::pad(f, |w| {
+            w.write_str(prefix)?;
+            self.write_encoded(w, Base::Base16Lower)
+        })
+    }
+}
+
+impl core::fmt::UpperHex for Cid {
+    /// Writes the binary representation of the CID in uppercase hex, without allocating.
+    ///
+    /// The alternate form (`{:#X}`) is prefixed with `0x`.
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let prefix = if f.alternate() { "0x" } else { "" };
+        encoding::pad(f, |w| {
+            w.write_str(prefix)?;
+            self.write_encoded(w, Base::Base16Upper)
+        })
+    }
+}
+
+impl core::str::FromStr for Cid {
     type Err = Error;
 
     fn from_str(cid_str: &str) -> Result<Self> {
@@ -145,28 +804,157 @@ impl TryFrom<&str> for Cid {
     type Error = Error;
 
     fn try_from(cid_str: &str) -> Result<Self> {
-        static IPFS_DELIMETER: &str = "/ipfs/";
+        Self::from_str_with_limits(cid_str, &Limits::DEFAULT)
+    }
+}
 
-        let hash = match cid_str.find(IPFS_DELIMETER) {
-            Some(index) => &cid_str[index + IPFS_DELIMETER.len()..],
-            _ => cid_str,
-        };
+/// Decode the string representation of a CID into its base and bytes, without parsing them.
+pub(crate) fn decode_str(cid_str: &str) -> Result<(Base, Vec<u8>)> {
+    decode_str_with_limits(cid_str, &Limits::DEFAULT)
+}
 
-        if hash.len() < 2 {
-            return Err(Error::InputTooShort);
+/// Decode the string representation of a CID into its base and bytes, rejecting strings that
+/// are too long to encode a CID within the limits before decoding them.
+fn decode_str_with_limits(cid_str: &str, limits: &Limits) -> Result<(Base, Vec<u8>)> {
+    static IPFS_DELIMETER: &str = "/ipfs/";
+
+    let (hash, offset) = match cid_str.find(IPFS_DELIMETER) {
+        Some(index) => {
+            let start = index + IPFS_DELIMETER.len();
+            (&cid_str[start..], cid_str[..start].chars().count())
         }
+        _ => (cid_str, 0),
+    };
 
-        let decoded = if Version::is_v0_str(hash) {
-            Base::Base58Btc.decode(hash)?
-        } else {
-            let (_, decoded) = multibase::decode(hash)?;
-            decoded
-        };
+    if hash.len() < 2 {
+        return Err(Error::InputTooShort);
+    }
+    if hash.len() > limits.max_str_len() {
+        return Err(Error::InputTooLong);
+    }
 
-        Self::try_from(decoded)
+    if Version::is_v0_str(hash) {
+        let decoded = Base::Base58Btc
+            .decode(hash)
+            .map_err(|err| multibase_error(err, Base::Base58Btc, hash, offset))?;
+        Ok((Base::Base58Btc, decoded))
+    } else {
+        decode_multibase(hash, offset)
     }
 }
 
+/// Decode a multibase string, which starts at the character position `offset` of the input.
+///
+/// Unlike `multibase::decode`, the case of the payload is ignored for bases whose alphabet is
+/// case-insensitive, so that e.g. case-folded base32 strings are accepted.
+fn decode_multibase(input: &str, offset: usize) -> Result<(Base, Vec<u8>)> {
+    let code = input.chars().next().ok_or(Error::InputTooShort)?;
+    let base = Base::from_code(code).map_err(|err| Error::Malformed {
+        component: Component::Multibase,
+        offset,
+        source: Some(Cause::Multibase(err)),
+    })?;
+    let data = &input[code.len_utf8()..];
+
+    let data = match base {
+        Base::Base16Lower
+        | Base::Base32Lower
+        | Base::Base32PadLower
+        | Base::Base32HexLower
+        | Base::Base32HexPadLower
+        | Base::Base36Lower
+            if data.bytes().any(|b| b.is_ascii_uppercase()) =>
+        {
+            Cow::Owned(data.to_ascii_lowercase())
+        }
+        Base::Base16Upper
+        | Base::Base32Upper
+        | Base::Base32PadUpper
+        | Base::Base32HexUpper
+        | Base::Base32HexPadUpper
+        | Base::Base36Upper
+            if data.bytes().any(|b| b.is_ascii_lowercase()) =>
+        {
+            Cow::Owned(data.to_ascii_uppercase())
+        }
+        _ => Cow::Borrowed(data),
+    };
+    let decoded = base
+        .decode(data.as_ref())
+        .map_err(|err| multibase_error(err, base, &data, offset + 1))?;
+    Ok((base, decoded))
+}
+
+/// The error for `data` that failed to decode in the given base with `err`, pointing at its first
+/// character that isn't part of the alphabet, or at its end if they all are. `data` starts at the
+/// character position `offset` of the input.
+fn multibase_error(err: multibase::Error, base: Base, data: &str, offset: usize) -> Error {
+    let position = data
+        .chars()
+        .position(|c| !in_alphabet(base, c))
+        .unwrap_or_else(|| data.chars().count());
+    Error::Malformed {
+        component: Component::Multibase,
+        offset: offset + position,
+        source: Some(Cause::Multibase(err)),
+    }
+}
+
+/// Whether the character is part of the alphabet of the base.
+fn in_alphabet(base: Base, c: char) -> bool {
+    if c == '=' {
+        return is_padded(base);
+    }
+    // Eight characters are a whole number of bytes in every base.
+    let mut buf = [0; 4];
+    base.decode(c.encode_utf8(&mut buf).repeat(8)).is_ok()
+}
+
+/// Build a multihash from its encoded bytes, taking the digest after the header as is.
+///
+/// Panics if the header can't be decoded.
+fn wrap_unchecked(hash: &[u8]) -> Multihash {
+    let (code, rest) = varint_decode::u64(hash).expect("invalid multihash code");
+    let (_, digest) = varint_decode::usize(rest).expect("invalid multihash length");
+    multihash::wrap(Code::from_u64(code), digest)
+}
+
+/// Return the multicodec name of a multihash code, or `None` for custom codes.
+fn multihash_name(code: Code) -> Option<&'static str> {
+    Some(match code {
+        Code::Identity => "identity",
+        Code::Sha1 => "sha1",
+        Code::Sha2_256 => "sha2-256",
+        Code::Sha2_512 => "sha2-512",
+        Code::Sha3_224 => "sha3-224",
+        Code::Sha3_256 => "sha3-256",
+        Code::Sha3_384 => "sha3-384",
+        Code::Sha3_512 => "sha3-512",
+        Code::Keccak224 => "keccak-224",
+        Code::Keccak256 => "keccak-256",
+        Code::Keccak384 => "keccak-384",
+        Code::Keccak512 => "keccak-512",
+        Code::Blake2b256 => "blake2b-256",
+        Code::Blake2b512 => "blake2b-512",
+        Code::Blake2s128 => "blake2s-128",
+        Code::Blake2s256 => "blake2s-256",
+        Code::Custom(_) => return None,
+    })
+}
+
+/// Whether the base is padded.
+fn is_padded(base: Base) -> bool {
+    matches!(
+        base,
+        Base::Base32PadLower
+            | Base::Base32PadUpper
+            | Base::Base32HexPadLower
+            | Base::Base32HexPadUpper
+            | Base::Base64Pad
+            | Base::Base64UrlPad
+    )
+}
+
 impl TryFrom<Vec<u8>> for Cid {
     type Error = Error;
 
@@ -179,19 +967,18 @@ impl TryFrom<&[u8]> for Cid {
     type Error = Error;
 
     fn try_from(bytes: &[u8]) -> Result<Self> {
-        if Version::is_v0_binary(bytes) {
-            let mh = MultihashRef::from_slice(bytes)?.to_owned();
-            Cid::new_v0(mh)
-        } else {
-            let (raw_version, remain) = varint_decode::u64(&bytes)?;
-            let version = Version::from(raw_version)?;
-
-            let (raw_codec, hash) = varint_decode::u64(&remain)?;
-            let codec = Codec::from(raw_codec)?;
+        Self::from_bytes_with_limits(bytes, &Limits::DEFAULT)
+    }
+}
 
-            let mh = MultihashRef::from_slice(hash)?.to_owned();
+impl From<Cid> for Vec<u8> {
+    fn from(cid: Cid) -> Self {
+        cid.to_bytes()
+    }
+}
 
-            Cid::new(version, codec, mh)
-        }
+impl From<&Cid> for Vec<u8> {
+    fn from(cid: &Cid) -> Self {
+        cid.to_bytes()
     }
 }
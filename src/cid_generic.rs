@@ -0,0 +1,354 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::Hash;
+
+use multibase::Base;
+use multihash::Code;
+use unsigned_varint::encode as varint_encode;
+
+use crate::cid::{self, Cid};
+use crate::cid_ref::CidRef;
+use crate::codec::Codec;
+use crate::encoding;
+use crate::error::{Error, Result};
+use crate::key::CidKey;
+use crate::version::Version;
+
+/// A table of multihash codes, the hash functions a [`CidGeneric`] accepts.
+///
+/// `multihash::Code` is the table of the codes known to `multihash`, which keeps any other code as
+/// `Code::Custom`. Applications with private-use codes can restrict CIDs to their own table.
+pub trait CodeTable: Copy + Eq + fmt::Debug {
+    /// Return the entry of a multihash code, or `None` if it isn't in the table.
+    fn from_code(code: u64) -> Option<Self>;
+
+    /// Return the multihash code of the entry.
+    fn code(&self) -> u64;
+}
+
+impl CodeTable for Code {
+    fn from_code(code: u64) -> Option<Self> {
+        Some(Code::from_u64(code))
+    }
+
+    fn code(&self) -> u64 {
+        self.to_u64()
+    }
+}
+
+/// A CID whose multihash code is an entry of the code table `H`.
+///
+/// Parsing rejects CIDs with a multihash code that isn't in the table with
+/// `Error::UnsupportedMultihash`. Equality, ordering and hashing are the same as for [`Cid`], which
+/// it converts to and from:
+///
+/// ```
+/// use cid::{CidGeneric, Codec, CodeTable, Error};
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// enum MyCode {
+///     Sha2_256,
+///     Private,
+/// }
+///
+/// impl CodeTable for MyCode {
+///     fn from_code(code: u64) -> Option<Self> {
+///         match code {
+///             0x12 => Some(MyCode::Sha2_256),
+///             0x30_0000 => Some(MyCode::Private),
+///             _ => None,
+///         }
+///     }
+///
+///     fn code(&self) -> u64 {
+///         match self {
+///             MyCode::Sha2_256 => 0x12,
+///             MyCode::Private => 0x30_0000,
+///         }
+///     }
+/// }
+///
+/// let cid = CidGeneric::new_v1(Codec::Raw, MyCode::Private, &[0xab; 16]);
+/// let parsed: CidGeneric<MyCode> = cid.to_string().parse().unwrap();
+/// assert_eq!(parsed, cid);
+/// assert_eq!(parsed.code(), MyCode::Private);
+///
+/// // The empty raw block hashed with SHA2-512, which isn't in the table.
+/// let sha2_512 = concat!(
+///     "bafkrgqgpqpqtk7xpxc67cvbikdlg3aah2yqoibilk4k5za7uveq5g3hjzzd5b",
+///     "uj4lwc7fmh7qmmnfb365qxwhojrxvduc6ubuu4de6xze7nd4",
+/// );
+/// assert_eq!(
+///     sha2_512.parse::<CidGeneric<MyCode>>(),
+///     Err(Error::UnsupportedMultihash)
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct CidGeneric<H> {
+    version: Version,
+    codec: Codec,
+    code: H,
+    /// The encoded multihash.
+    hash: Vec<u8>,
+    /// The offset of the digest in `hash`.
+    digest_start: usize,
+}
+
+impl<H: CodeTable> CidGeneric<H> {
+    /// Create a new CIDv0 from a SHA2-256 digest.
+    ///
+    /// Returns `Error::UnsupportedMultihash` if SHA2-256 isn't in the table.
+    pub fn new_v0(digest: &[u8]) -> Result<Self> {
+        if digest.len() != 32 {
+            return Err(Error::InvalidCidV0Multihash);
+        }
+        let code = H::from_code(Code::Sha2_256.to_u64()).ok_or(Error::UnsupportedMultihash)?;
+        Ok(Self::new_unchecked(
+            Version::V0,
+            Codec::DagProtobuf,
+            code,
+            digest,
+        ))
+    }
+
+    /// Create a new CIDv1.
+    pub fn new_v1(codec: Codec, code: H, digest: &[u8]) -> Self {
+        Self::new_unchecked(Version::V1, codec, code, digest)
+    }
+
+    fn new_unchecked(version: Version, codec: Codec, code: H, digest: &[u8]) -> Self {
+        let mut buf = varint_encode::u64_buffer();
+        let mut hash = varint_encode::u64(code.code(), &mut buf).to_vec();
+        let mut buf = varint_encode::usize_buffer();
+        hash.extend_from_slice(varint_encode::usize(digest.len(), &mut buf));
+        let digest_start = hash.len();
+        hash.extend_from_slice(digest);
+        CidGeneric {
+            version,
+            codec,
+            code,
+            hash,
+            digest_start,
+        }
+    }
+
+    /// Create a new `CidGeneric` from encoded bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::try_from(CidRef::from_bytes(bytes)?)
+    }
+
+    /// The version of the CID.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// The codec of the CID.
+    pub fn codec(&self) -> Codec {
+        self.codec
+    }
+
+    /// The multihash code of the CID.
+    pub fn code(&self) -> H {
+        self.code
+    }
+
+    /// The digest of the multihash of the CID.
+    pub fn digest(&self) -> &[u8] {
+        &self.hash[self.digest_start..]
+    }
+
+    /// The encoded multihash of the CID.
+    pub fn hash_bytes(&self) -> &[u8] {
+        &self.hash
+    }
+
+    /// Convert to a [`Cid`].
+    pub fn to_cid(&self) -> Cid {
+        let hash = multihash::wrap(Code::from_u64(self.code.code()), self.digest());
+        Cid::new(self.version, self.codec, hash).expect("CidGeneric is a valid CID")
+    }
+
+    /// Return the length of the encoded bytes of the CID.
+    pub fn encoded_len(&self) -> usize {
+        let mut bufs = buffers();
+        self.parts(&mut bufs).iter().map(|part| part.len()).sum()
+    }
+
+    /// Convert CID to encoded bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bufs = buffers();
+        self.parts(&mut bufs).concat()
+    }
+
+    /// Write the encoded bytes of the CID to a writer, without allocating.
+    ///
+    /// Returns the number of bytes written, which is always `encoded_len()`.
+    #[cfg(feature = "std")]
+    pub fn write_bytes<W: std::io::Write>(&self, mut w: W) -> std::io::Result<usize> {
+        let mut bufs = buffers();
+        let mut len = 0;
+        for part in self.parts(&mut bufs).iter() {
+            w.write_all(part)?;
+            len += part.len();
+        }
+        Ok(len)
+    }
+
+    /// The encoded parts of the CID: version, codec and multihash.
+    ///
+    /// Version and codec are empty for CIDv0.
+    fn parts<'a>(&'a self, bufs: &'a mut Buffers) -> [&'a [u8]; 3] {
+        let [version, codec] = bufs;
+        match self.version {
+            Version::V0 => [&[], &[], &self.hash],
+            Version::V1 => [
+                varint_encode::u64(self.version.into(), version),
+                varint_encode::u64(self.codec.into(), codec),
+                &self.hash,
+            ],
+        }
+    }
+}
+
+/// Buffers for the version and codec varints of a CID.
+type Buffers = [[u8; 10]; 2];
+
+fn buffers() -> Buffers {
+    [varint_encode::u64_buffer(); 2]
+}
+
+impl<H: CodeTable> CidKey for CidGeneric<H> {
+    fn cid_key(&self) -> (u64, u64, &[u8]) {
+        (self.version.into(), self.codec.into(), &self.hash)
+    }
+}
+
+impl<H: CodeTable> Hash for CidGeneric<H> {
+    /// Hashes the same as [`Cid`].
+    fn hash<S: core::hash::Hasher>(&self, state: &mut S) {
+        self.cid_key().hash(state);
+    }
+}
+
+impl<H: CodeTable> PartialOrd for CidGeneric<H> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<H: CodeTable> Ord for CidGeneric<H> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cid_key().cmp(&other.cid_key())
+    }
+}
+
+impl<H: CodeTable> PartialEq<Cid> for CidGeneric<H> {
+    fn eq(&self, other: &Cid) -> bool {
+        self.cid_key() == other.cid_key()
+    }
+}
+
+impl<H: CodeTable> PartialEq<CidGeneric<H>> for Cid {
+    fn eq(&self, other: &CidGeneric<H>) -> bool {
+        other == self
+    }
+}
+
+impl<H: CodeTable> fmt::Debug for CidGeneric<H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CidGeneric")
+            .field("version", &self.version)
+            .field("codec", &self.codec)
+            .field("code", &self.code)
+            .field("digest", &self.digest())
+            .finish()
+    }
+}
+
+impl<H: CodeTable> fmt::Display for CidGeneric<H> {
+    /// Writes the CID in base58btc for CIDv0 and base32 lower for CIDv1, without allocating.
+    ///
+    /// The width, fill and alignment of the formatter are respected.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut bufs = buffers();
+        let parts = self.parts(&mut bufs);
+        encoding::pad(f, |w| match self.version {
+            Version::V0 => encoding::write_base(w, Base::Base58Btc, &parts),
+            Version::V1 => {
+                w.write_char(Base::Base32Lower.code())?;
+                encoding::write_base(w, Base::Base32Lower, &parts)
+            }
+        })
+    }
+}
+
+impl<'a, H: CodeTable> TryFrom<CidRef<'a>> for CidGeneric<H> {
+    type Error = Error;
+
+    fn try_from(cid: CidRef<'a>) -> Result<Self> {
+        let hash = cid.hash();
+        let code = H::from_code(hash.algorithm().to_u64()).ok_or(Error::UnsupportedMultihash)?;
+        Ok(Self::new_unchecked(
+            cid.version(),
+            cid.codec(),
+            code,
+            hash.digest(),
+        ))
+    }
+}
+
+impl<H: CodeTable> TryFrom<&Cid> for CidGeneric<H> {
+    type Error = Error;
+
+    fn try_from(cid: &Cid) -> Result<Self> {
+        let code =
+            H::from_code(cid.hash.algorithm().to_u64()).ok_or(Error::UnsupportedMultihash)?;
+        Ok(Self::new_unchecked(
+            cid.version,
+            cid.codec,
+            code,
+            cid.hash.digest(),
+        ))
+    }
+}
+
+impl<H: CodeTable> TryFrom<Cid> for CidGeneric<H> {
+    type Error = Error;
+
+    fn try_from(cid: Cid) -> Result<Self> {
+        Self::try_from(&cid)
+    }
+}
+
+impl<H: CodeTable> TryFrom<&[u8]> for CidGeneric<H> {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl<H: CodeTable> TryFrom<&str> for CidGeneric<H> {
+    type Error = Error;
+
+    fn try_from(cid_str: &str) -> Result<Self> {
+        let (_, decoded) = cid::decode_str(cid_str)?;
+        Self::from_bytes(&decoded)
+    }
+}
+
+impl<H: CodeTable> core::str::FromStr for CidGeneric<H> {
+    type Err = Error;
+
+    fn from_str(cid_str: &str) -> Result<Self> {
+        Self::try_from(cid_str)
+    }
+}
+
+impl<H: CodeTable> From<CidGeneric<H>> for Cid {
+    fn from(cid: CidGeneric<H>) -> Self {
+        cid.to_cid()
+    }
+}
@@ -0,0 +1,380 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+
+use multibase::Base;
+use multihash::Code;
+use unsigned_varint::encode as varint_encode;
+
+use crate::cid::{self, Cid};
+use crate::cid_ref::CidRef;
+use crate::codec::Codec;
+use crate::encoding;
+use crate::error::{Error, Result};
+use crate::version::Version;
+
+/// A CID that stores the digest of its multihash inline, in an array of `S` bytes.
+///
+/// Unlike [`Cid`] it is `Copy` and never allocates, but digests larger than `S` bytes are
+/// rejected. E.g. `CidN<32>` holds any CID with a 256-bit digest, like SHA2-256, and `CidN<64>` any
+/// CID with a 512-bit digest, like SHA2-512, which [`Cid`] stores on the heap.
+///
+/// It can be built in a const context, to declare well-known CIDs as `const` or `static` items:
+///
+/// ```
+/// use cid::{CidN, Version};
+///
+/// /// The CID of the empty dag-pb block.
+/// const EMPTY_DAG_PB: CidN<32> = CidN::from_bytes_const(&[
+///     0x12, 0x20, 0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14, 0x9a, 0xfb, 0xf4, 0xc8, 0x99,
+///     0x6f, 0xb9, 0x24, 0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c, 0xa4, 0x95, 0x99, 0x1b,
+///     0x78, 0x52, 0xb8, 0x55,
+/// ]);
+/// assert_eq!(EMPTY_DAG_PB.version(), Version::V0);
+/// assert_eq!(
+///     EMPTY_DAG_PB.to_string(),
+///     "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n"
+/// );
+/// ```
+///
+/// Equality and ordering are the same as for [`Cid`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CidN<const S: usize> {
+    version: Version,
+    codec: Codec,
+    code: u64,
+    size: usize,
+    /// The digest, zero padded after `size` bytes.
+    digest: [u8; S],
+}
+
+impl<const S: usize> CidN<S> {
+    /// Create a new CIDv0 from a SHA2-256 digest.
+    pub fn new_v0(digest: &[u8]) -> Result<Self> {
+        if digest.len() != 32 {
+            return Err(Error::InvalidCidV0Multihash);
+        }
+        Self::new_unchecked(Version::V0, Codec::DagProtobuf, Code::Sha2_256, digest)
+    }
+
+    /// Create a new CIDv1.
+    pub fn new_v1(codec: Codec, code: Code, digest: &[u8]) -> Result<Self> {
+        Self::new_unchecked(Version::V1, codec, code, digest)
+    }
+
+    fn new_unchecked(version: Version, codec: Codec, code: Code, digest: &[u8]) -> Result<Self> {
+        if digest.len() > S {
+            return Err(Error::DigestTooLarge);
+        }
+        let mut buf = [0; S];
+        buf[..digest.len()].copy_from_slice(digest);
+        Ok(CidN {
+            version,
+            codec,
+            code: code.to_u64(),
+            size: digest.len(),
+            digest: buf,
+        })
+    }
+
+    /// Create a new CIDv0 from a SHA2-256 digest, in a const context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `S` isn't 32.
+    pub const fn from_v0_digest(digest: [u8; S]) -> Self {
+        assert!(S == 32, "CIDv0 requires a 32 byte SHA2-256 digest");
+        CidN {
+            version: Version::V0,
+            codec: Codec::DagProtobuf,
+            code: 0x12,
+            size: S,
+            digest,
+        }
+    }
+
+    /// Create a new CIDv1 from its multihash code and a digest of `S` bytes, in a const context.
+    pub const fn from_v1_digest(codec: Codec, code: u64, digest: [u8; S]) -> Self {
+        CidN {
+            version: Version::V1,
+            codec,
+            code,
+            size: S,
+            digest,
+        }
+    }
+
+    /// Create a new `CidN` from encoded bytes, without allocating.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::try_from(CidRef::from_bytes(bytes)?)
+    }
+
+    /// Create a new `CidN` from encoded bytes, in a const context.
+    ///
+    /// Like [`Cid::from_bytes_strict`], it rejects varints that aren't minimally encoded, bytes
+    /// after the multihash and CIDv0 with an explicit version and codec.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bytes aren't a valid CID, or if its digest is larger than `S` bytes. In a
+    /// `const` or `static` item, this fails the build.
+    pub const fn from_bytes_const(bytes: &[u8]) -> Self {
+        let (version, codec, start) = if bytes.len() == 34 && bytes[0] == 0x12 && bytes[1] == 0x20 {
+            (Version::V0, Codec::DagProtobuf, 0)
+        } else {
+            let (version, offset) = const_varint(bytes, 0);
+            assert!(version == 1, "unsupported CID version");
+            let (codec, offset) = const_varint(bytes, offset);
+            (Version::V1, Codec::from_code(codec), offset)
+        };
+        let (code, offset) = const_varint(bytes, start);
+        let (size, offset) = const_varint(bytes, offset);
+        assert!(size <= S as u64, "digest too large");
+        let size = size as usize;
+        assert!(offset + size == bytes.len(), "digest length mismatch");
+
+        let mut digest = [0; S];
+        let mut i = 0;
+        while i < size {
+            digest[i] = bytes[offset + i];
+            i += 1;
+        }
+        CidN {
+            version,
+            codec,
+            code,
+            size,
+            digest,
+        }
+    }
+
+    /// The version of the CID.
+    pub const fn version(&self) -> Version {
+        self.version
+    }
+
+    /// The codec of the CID.
+    pub const fn codec(&self) -> Codec {
+        self.codec
+    }
+
+    /// The multihash type of the CID.
+    pub fn hash_code(&self) -> Code {
+        Code::from_u64(self.code)
+    }
+
+    /// The digest of the multihash of the CID.
+    pub const fn digest(&self) -> &[u8] {
+        self.digest.split_at(self.size).0
+    }
+
+    /// Convert to a [`Cid`].
+    ///
+    /// This allocates if the multihash is too large to be stored inline by [`Cid`].
+    pub fn to_cid(&self) -> Cid {
+        let hash = multihash::wrap(self.hash_code(), self.digest());
+        Cid::new(self.version, self.codec, hash).expect("CidN is a valid CID")
+    }
+
+    /// Return the length of the encoded bytes of the CID.
+    pub fn encoded_len(&self) -> usize {
+        let mut bufs = buffers();
+        self.parts(&mut bufs).iter().map(|part| part.len()).sum()
+    }
+
+    /// Convert CID to encoded bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bufs = buffers();
+        self.parts(&mut bufs).concat()
+    }
+
+    /// Write the encoded bytes of the CID to a writer, without allocating.
+    ///
+    /// Returns the number of bytes written, which is always `encoded_len()`.
+    #[cfg(feature = "std")]
+    pub fn write_bytes<W: std::io::Write>(&self, mut w: W) -> std::io::Result<usize> {
+        let mut bufs = buffers();
+        let mut len = 0;
+        for part in self.parts(&mut bufs).iter() {
+            w.write_all(part)?;
+            len += part.len();
+        }
+        Ok(len)
+    }
+
+    /// The encoded parts of the CID: version, codec, multihash code, digest size and digest.
+    ///
+    /// Version and codec are empty for CIDv0.
+    fn parts<'a>(&'a self, bufs: &'a mut Buffers) -> [&'a [u8]; 5] {
+        let [version, codec, code, size] = bufs;
+        let (version, codec) = match self.version {
+            Version::V0 => (&[][..], &[][..]),
+            Version::V1 => (
+                varint_encode::u64(self.version.into(), version),
+                varint_encode::u64(self.codec.into(), codec),
+            ),
+        };
+        [
+            version,
+            codec,
+            varint_encode::u64(self.code, code),
+            varint_encode::usize(self.size, size),
+            self.digest(),
+        ]
+    }
+}
+
+/// Decode the unsigned varint at `offset`, in a const context.
+///
+/// Returns the value and the offset after the varint. Panics if it is truncated, overflows or
+/// isn't minimally encoded.
+const fn const_varint(bytes: &[u8], mut offset: usize) -> (u64, usize) {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        assert!(offset < bytes.len(), "truncated varint");
+        let byte = bytes[offset];
+        assert!(shift < 63 || byte <= 1, "varint overflow");
+        value |= ((byte & 0x7f) as u64) << shift;
+        offset += 1;
+        if byte & 0x80 == 0 {
+            assert!(byte != 0 || shift == 0, "varint not minimally encoded");
+            return (value, offset);
+        }
+        shift += 7;
+    }
+}
+
+/// Buffers for the varints of the encoded parts of a CID.
+type Buffers = [[u8; 10]; 4];
+
+fn buffers() -> Buffers {
+    [varint_encode::u64_buffer(); 4]
+}
+
+impl<const S: usize> PartialOrd for CidN<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const S: usize> Ord for CidN<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (mut a, mut b) = (buffers(), buffers());
+        let (a, b) = (self.parts(&mut a), other.parts(&mut b));
+        (self.version, u64::from(self.codec))
+            .cmp(&(other.version, u64::from(other.codec)))
+            .then_with(|| {
+                a[2..]
+                    .iter()
+                    .copied()
+                    .flatten()
+                    .cmp(b[2..].iter().copied().flatten())
+            })
+    }
+}
+
+impl<const S: usize> PartialEq<Cid> for CidN<S> {
+    fn eq(&self, other: &Cid) -> bool {
+        self.version == other.version
+            && self.codec == other.codec
+            && self.code == other.hash.algorithm().to_u64()
+            && self.digest() == other.hash.digest()
+    }
+}
+
+impl<const S: usize> PartialEq<CidN<S>> for Cid {
+    fn eq(&self, other: &CidN<S>) -> bool {
+        other == self
+    }
+}
+
+impl<const S: usize> fmt::Debug for CidN<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CidN")
+            .field("version", &self.version)
+            .field("codec", &self.codec)
+            .field("hash_code", &self.hash_code())
+            .field("digest", &self.digest())
+            .finish()
+    }
+}
+
+impl<const S: usize> fmt::Display for CidN<S> {
+    /// Writes the CID in base58btc for CIDv0 and base32 lower for CIDv1, without allocating.
+    ///
+    /// The width, fill and alignment of the formatter are respected.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut bufs = buffers();
+        let parts = self.parts(&mut bufs);
+        encoding::pad(f, |w| match self.version {
+            Version::V0 => encoding::write_base(w, Base::Base58Btc, &parts),
+            Version::V1 => {
+                w.write_char(Base::Base32Lower.code())?;
+                encoding::write_base(w, Base::Base32Lower, &parts)
+            }
+        })
+    }
+}
+
+impl<'a, const S: usize> TryFrom<CidRef<'a>> for CidN<S> {
+    type Error = Error;
+
+    fn try_from(cid: CidRef<'a>) -> Result<Self> {
+        let hash = cid.hash();
+        Self::new_unchecked(cid.version(), cid.codec(), hash.algorithm(), hash.digest())
+    }
+}
+
+impl<const S: usize> TryFrom<&Cid> for CidN<S> {
+    type Error = Error;
+
+    fn try_from(cid: &Cid) -> Result<Self> {
+        Self::new_unchecked(
+            cid.version,
+            cid.codec,
+            cid.hash.algorithm(),
+            cid.hash.digest(),
+        )
+    }
+}
+
+impl<const S: usize> TryFrom<Cid> for CidN<S> {
+    type Error = Error;
+
+    fn try_from(cid: Cid) -> Result<Self> {
+        Self::try_from(&cid)
+    }
+}
+
+impl<const S: usize> TryFrom<&[u8]> for CidN<S> {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl<const S: usize> TryFrom<&str> for CidN<S> {
+    type Error = Error;
+
+    fn try_from(cid_str: &str) -> Result<Self> {
+        let (_, decoded) = cid::decode_str(cid_str)?;
+        Self::from_bytes(&decoded)
+    }
+}
+
+impl<const S: usize> core::str::FromStr for CidN<S> {
+    type Err = Error;
+
+    fn from_str(cid_str: &str) -> Result<Self> {
+        Self::try_from(cid_str)
+    }
+}
+
+impl<const S: usize> From<CidN<S>> for Cid {
+    fn from(cid: CidN<S>) -> Self {
+        cid.to_cid()
+    }
+}
@@ -0,0 +1,324 @@
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+
+use multihash::{Code, MultihashRef};
+use unsigned_varint::decode as varint_decode;
+
+use crate::cid::Cid;
+use crate::codec::Codec;
+use crate::error::{Cause, Component, Error, Result};
+use crate::limits::Limits;
+use crate::prefix::Prefix;
+use crate::version::Version;
+
+/// A borrowed CID, backed by its encoded bytes.
+///
+/// Decoding into a `CidRef` validates the input just like decoding into a [`Cid`] does, but
+/// never allocates.
+#[derive(Clone, Copy, Debug)]
+pub struct CidRef<'a> {
+    version: Version,
+    codec: Codec,
+    hash: MultihashRef<'a>,
+    bytes: &'a [u8],
+}
+
+impl<'a> CidRef<'a> {
+    /// Create a new `CidRef` from encoded bytes.
+    ///
+    /// Codecs unknown to this crate are decoded as `Codec::Other`, check [`Codec::is_known`] to
+    /// reject them.
+    ///
+    /// The input is checked against [`Limits::DEFAULT`].
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<CidRef<'a>> {
+        Self::from_bytes_with_limits(bytes, &Limits::DEFAULT)
+    }
+
+    /// Create a new `CidRef` from encoded bytes, checked against the given limits.
+    pub fn from_bytes_with_limits(bytes: &'a [u8], limits: &Limits) -> Result<CidRef<'a>> {
+        Self::parse(bytes, limits, false)
+    }
+
+    /// Create a new `CidRef` from encoded bytes, rejecting anything but the canonical encoding.
+    ///
+    /// Unlike [`CidRef::from_bytes`], this rejects varints that aren't minimally encoded
+    /// (`Error::NonMinimalVarint`), bytes after the multihash (`Error::TrailingBytes`) and CIDv0
+    /// with an explicit version and codec (`Error::InvalidCidVersion`).
+    pub fn from_bytes_strict(bytes: &'a [u8]) -> Result<CidRef<'a>> {
+        Self::parse(bytes, &Limits::DEFAULT, true)
+    }
+
+    /// Decode a `CidRef` from the start of a buffer, which may contain more data after it.
+    ///
+    /// Returns the CID and the number of bytes it takes up. The CID is checked against
+    /// [`Limits::DEFAULT`].
+    pub fn decode(bytes: &'a [u8]) -> Result<(CidRef<'a>, usize)> {
+        let len = encoded_len(bytes)?;
+        Ok((Self::from_bytes(&bytes[..len])?, len))
+    }
+
+    fn parse(bytes: &'a [u8], limits: &Limits, strict: bool) -> Result<CidRef<'a>> {
+        if bytes.len() > limits.max_len {
+            return Err(Error::InputTooLong);
+        }
+
+        if Version::is_v0_binary(bytes) {
+            let hash = MultihashRef::from_slice(bytes)
+                .map_err(|err| malformed_by(Component::Multihash, 0, Cause::Multihash(err)))?;
+            return Ok(CidRef {
+                version: Version::V0,
+                codec: Codec::DagProtobuf,
+                hash,
+                bytes,
+            });
+        }
+
+        let (raw_version, remain) = decode_varint(bytes, 0, Component::Version, strict)?;
+        let version = Version::from(raw_version)?;
+        if strict && version == Version::V0 {
+            return Err(Error::InvalidCidVersion);
+        }
+
+        let offset = bytes.len() - remain.len();
+        let (raw_codec, hash) = decode_varint(remain, offset, Component::Codec, strict)?;
+        let codec = Codec::from_code(raw_codec);
+
+        let offset = bytes.len() - hash.len();
+        let (_, remain) = decode_varint(hash, offset, Component::Multihash, strict)?;
+        let (len, digest) = decode_varint(
+            remain,
+            bytes.len() - remain.len(),
+            Component::Multihash,
+            strict,
+        )?;
+        let digest_offset = bytes.len() - digest.len();
+        if digest.len() as u64 > len {
+            if strict {
+                return Err(Error::TrailingBytes);
+            }
+            return Err(malformed(
+                Component::Multihash,
+                digest_offset + len as usize,
+            ));
+        }
+        if (digest.len() as u64) < len {
+            return Err(malformed(Component::Multihash, bytes.len()));
+        }
+
+        let hash = MultihashRef::from_slice(hash)
+            .map_err(|err| malformed_by(Component::Multihash, offset, Cause::Multihash(err)))?;
+        if hash.digest().len() > limits.max_digest_len {
+            return Err(Error::DigestTooLarge);
+        }
+
+        match version {
+            Version::V0 => {
+                if codec != Codec::DagProtobuf {
+                    return Err(Error::InvalidCidV0Codec);
+                }
+                if hash.algorithm() != Code::Sha2_256 {
+                    return Err(Error::InvalidCidV0Multihash);
+                }
+                // A CIDv0 is only ever encoded as its bare multihash.
+                Ok(CidRef {
+                    version,
+                    codec,
+                    hash,
+                    bytes: hash.as_bytes(),
+                })
+            }
+            Version::V1 => Ok(CidRef {
+                version,
+                codec,
+                hash,
+                bytes,
+            }),
+        }
+    }
+
+    /// The version of the CID.
+    pub const fn version(&self) -> Version {
+        self.version
+    }
+
+    /// The codec of the CID.
+    pub const fn codec(&self) -> Codec {
+        self.codec
+    }
+
+    /// The multihash of the CID.
+    pub fn hash(&self) -> MultihashRef<'a> {
+        self.hash
+    }
+
+    /// The digest of the multihash, without its code and length.
+    pub fn digest(&self) -> &'a [u8] {
+        self.hash.digest()
+    }
+
+    /// The data embedded in an identity multihash, or `None` for any other multihash.
+    pub fn inline_data(&self) -> Option<&'a [u8]> {
+        match self.hash.algorithm() {
+            Code::Identity => Some(self.hash.digest()),
+            _ => None,
+        }
+    }
+
+    /// The encoded bytes of the CID.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Return the prefix of the CID.
+    pub fn prefix(&self) -> Prefix {
+        Prefix {
+            version: self.version,
+            codec: self.codec,
+            mh_type: self.hash.algorithm(),
+            mh_len: self.hash.digest().len(),
+        }
+    }
+
+    /// The key CIDs are ordered by, the same as for [`Cid`].
+    pub(crate) fn order_key(&self) -> (u64, u64, &'a [u8]) {
+        (self.version.into(), self.codec.into(), self.hash.as_bytes())
+    }
+
+    /// Builds a `Cid` that owns the data.
+    ///
+    /// This operation allocates if the multihash is larger than the 38 bytes [`Cid`] stores inline.
+    pub fn to_owned(&self) -> Cid {
+        Cid {
+            version: self.version,
+            codec: self.codec,
+            hash: self.hash.to_owned(),
+            base: None,
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for CidRef<'a> {
+    type Error = Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl<'a> From<CidRef<'a>> for Cid {
+    fn from(cid: CidRef<'a>) -> Self {
+        cid.to_owned()
+    }
+}
+
+impl<'a> PartialEq for CidRef<'a> {
+    /// Compares the version, codec and multihash, like `Ord` and `Hash`, so non-canonical
+    /// encodings of a CID are equal to the canonical one.
+    fn eq(&self, other: &Self) -> bool {
+        self.order_key() == other.order_key()
+    }
+}
+
+impl<'a> Eq for CidRef<'a> {}
+
+impl<'a> PartialOrd for CidRef<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for CidRef<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.order_key().cmp(&other.order_key())
+    }
+}
+
+impl<'a> core::hash::Hash for CidRef<'a> {
+    /// Hashes the same as the equal [`Cid`].
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.order_key().hash(state);
+    }
+}
+
+impl<'a> PartialEq<Cid> for CidRef<'a> {
+    fn eq(&self, other: &Cid) -> bool {
+        self.version == other.version && self.codec == other.codec && self.hash == other.hash
+    }
+}
+
+impl<'a> PartialEq<CidRef<'a>> for Cid {
+    fn eq(&self, other: &CidRef<'a>) -> bool {
+        other == self
+    }
+}
+
+impl<'a> PartialOrd<Cid> for CidRef<'a> {
+    fn partial_cmp(&self, other: &Cid) -> Option<Ordering> {
+        Some(self.order_key().cmp(&other.order_key()))
+    }
+}
+
+impl<'a> PartialOrd<CidRef<'a>> for Cid {
+    fn partial_cmp(&self, other: &CidRef<'a>) -> Option<Ordering> {
+        other.partial_cmp(self).map(Ordering::reverse)
+    }
+}
+
+/// The length of the CID at the start of `bytes`, from its varints and multihash length.
+fn encoded_len(bytes: &[u8]) -> Result<usize> {
+    if bytes.starts_with(&[0x12, 0x20]) {
+        // CIDv0, which can't be mistaken for CIDv1 as version 0x12 is invalid.
+        if bytes.len() < 34 {
+            return Err(malformed(Component::Multihash, bytes.len()));
+        }
+        return Ok(34);
+    }
+
+    let (_, remain) = decode_varint(bytes, 0, Component::Version, false)?;
+    let offset = bytes.len() - remain.len();
+    let (_, remain) = decode_varint(remain, offset, Component::Codec, false)?;
+    let offset = bytes.len() - remain.len();
+    let (_, remain) = decode_varint(remain, offset, Component::Multihash, false)?;
+    let offset = bytes.len() - remain.len();
+    let (digest_len, remain) = decode_varint(remain, offset, Component::Multihash, false)?;
+    let header_len = bytes.len() - remain.len();
+    match usize::try_from(digest_len) {
+        Ok(digest_len) if digest_len <= remain.len() => Ok(header_len + digest_len),
+        _ => Err(malformed(Component::Multihash, bytes.len())),
+    }
+}
+
+fn malformed(component: Component, offset: usize) -> Error {
+    Error::Malformed {
+        component,
+        offset,
+        source: None,
+    }
+}
+
+/// The error for a component that the underlying decoder failed on.
+fn malformed_by(component: Component, offset: usize, cause: Cause) -> Error {
+    Error::Malformed {
+        component,
+        offset,
+        source: Some(cause),
+    }
+}
+
+/// Decode an unsigned varint of the given component, which starts at `offset` in the CID.
+///
+/// In strict mode, encodings that aren't minimal, i.e. that end with a zero byte, are rejected.
+fn decode_varint(
+    bytes: &[u8],
+    offset: usize,
+    component: Component,
+    strict: bool,
+) -> Result<(u64, &[u8])> {
+    let (value, remain) = varint_decode::u64(bytes)
+        .map_err(|err| malformed_by(component, offset, Cause::VarInt(err)))?;
+    let len = bytes.len() - remain.len();
+    if strict && len > 1 && bytes[len - 1] == 0 {
+        return Err(Error::NonMinimalVarint);
+    }
+    Ok((value, remain))
+}
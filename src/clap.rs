@@ -0,0 +1,59 @@
+//! clap integration.
+//!
+//! [`Cid`] implements `ValueParserFactory`, so it can be used as argument type directly:
+//!
+//! ```ignore
+//! #[derive(Parser)]
+//! struct Args {
+//!     #[arg(value_parser)]
+//!     cid: Cid,
+//! }
+//! ```
+
+use core::convert::TryFrom;
+use std::ffi::OsStr;
+
+use clap::builder::{TypedValueParser, ValueParserFactory};
+use clap::error::ErrorKind;
+use clap::{Arg, Command};
+
+use crate::cid::Cid;
+
+/// Parser for CID arguments.
+///
+/// The error message tells whether the string couldn't be decoded as multibase, or which part of
+/// the decoded CID (version, codec, multihash) is invalid, and where.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CidValueParser;
+
+impl TypedValueParser for CidValueParser {
+    type Value = Cid;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let value = value
+            .to_str()
+            .ok_or_else(|| clap::Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+
+        Cid::try_from(value).map_err(|err| {
+            let arg = arg.map_or_else(|| "...".into(), |arg| arg.to_string());
+            clap::Error::raw(
+                ErrorKind::ValueValidation,
+                format!("invalid value '{}' for '{}': {}\n", value, arg, err),
+            )
+            .with_cmd(cmd)
+        })
+    }
+}
+
+impl ValueParserFactory for Cid {
+    type Parser = CidValueParser;
+
+    fn value_parser() -> Self::Parser {
+        CidValueParser
+    }
+}
@@ -1,21 +1,65 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::str::FromStr;
+
 use crate::error::{Error, Result};
 
 macro_rules! build_codec_enum {
-    {$( #[$attr:meta] $code:expr => $codec:ident, )*} => {
+    {$( #[$attr:meta] $code:expr => $codec:ident ($name:literal), )*} => {
         /// List of types currently supported in the multicodec spec.
-        #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+        ///
+        /// The variants are generated from the `ipld` and `filecoin` entries of the multicodec
+        /// table. New codecs are added as they get registered, so the enum is non-exhaustive.
+        ///
+        /// Codecs are compared, ordered and hashed by their code, so `Codec::Other(0x55)` is equal
+        /// to `Codec::Raw`.
+        #[derive(Clone, Copy, Debug)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+        #[non_exhaustive]
         pub enum Codec {
             $( #[$attr] $codec, )*
+            /// A codec unknown to this crate, with its code.
+            Other(u64),
         }
 
         impl Codec {
             /// Convert a number to the matching codec, or `Error` if unknown codec is matching.
-            pub fn from(raw: u64) -> Result<Codec> {
+            pub const fn from(raw: u64) -> Result<Codec> {
                 match raw {
                     $( $code => Ok(Self::$codec), )*
                     _ => Err(Error::UnknownCodec),
                 }
             }
+
+            /// Convert a number to the matching codec, or `Codec::Other` if it is unknown.
+            pub const fn from_code(raw: u64) -> Codec {
+                match raw {
+                    $( $code => Self::$codec, )*
+                    _ => Codec::Other(raw),
+                }
+            }
+
+            /// The canonical multicodec name, or `None` if the codec is unknown.
+            pub const fn name(&self) -> Option<&'static str> {
+                match self {
+                    $( Self::$codec => Some($name), )*
+                    Self::Other(_) => None,
+                }
+            }
+
+            /// Convert a canonical multicodec name to the matching codec.
+            pub fn from_name(name: &str) -> Option<Codec> {
+                match name {
+                    $( $name => Some(Self::$codec), )*
+                    _ => None,
+                }
+            }
+
+            /// Whether the codec is known to this crate.
+            pub fn is_known(&self) -> bool {
+                Self::from(u64::from(*self)).is_ok()
+            }
         }
 
         impl From<Codec> for u64 {
@@ -23,47 +67,64 @@ macro_rules! build_codec_enum {
             fn from(codec: Codec) -> u64 {
                 match codec {
                     $( Codec::$codec => $code, )*
+                    Codec::Other(code) => code,
                 }
             }
         }
     }
 }
 
-build_codec_enum! {
-    /// Raw binary
-    0x55 => Raw,
-    /// MerkleDAG protobuf
-    0x70 => DagProtobuf,
-    /// MerkleDAG cbor
-    0x71 => DagCBOR,
-    /// Raw Git object
-    0x78 => GitRaw,
-    /// Ethereum Block (RLP)
-    0x90 => EthereumBlock,
-    /// Ethereum Block List (RLP)
-    0x91 => EthereumBlockList,
-    /// Ethereum Transaction Trie (Eth-Trie)
-    0x92 => EthereumTxTrie,
-    /// Ethereum Transaction (RLP)
-    0x93 => EthereumTx,
-    /// Ethereum Transaction Receipt Trie (Eth-Trie)
-    0x94 => EthereumTxReceiptTrie,
-    /// Ethereum Transaction Receipt (RLP)
-    0x95 => EthereumTxReceipt,
-    /// Ethereum State Trie (Eth-Secure-Trie)
-    0x96 => EthereumStateTrie,
-    /// Ethereum Account Snapshot (RLP)
-    0x97 => EthereumAccountSnapshot,
-    /// Ethereum Contract Storage Trie (Eth-Secure-Trie)
-    0x98 => EthereumStorageTrie,
-    /// Bitcoin Block
-    0xb0 => BitcoinBlock,
-    /// Bitcoin Transaction
-    0xb1 => BitcoinTx,
-    /// Zcash Block
-    0xc0 => ZcashBlock,
-    /// Zcash Transaction
-    0xc1 => ZcashTx,
-    /// MerkleDAG json
-    0x0129 => DagJSON,
+include!(concat!(env!("OUT_DIR"), "/codec_table.rs"));
+
+impl PartialEq for Codec {
+    fn eq(&self, other: &Self) -> bool {
+        u64::from(*self) == u64::from(*other)
+    }
+}
+
+impl Eq for Codec {}
+
+impl PartialOrd for Codec {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Codec {
+    fn cmp(&self, other: &Self) -> Ordering {
+        u64::from(*self).cmp(&u64::from(*other))
+    }
+}
+
+impl Hash for Codec {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        u64::from(*self).hash(state);
+    }
+}
+
+impl fmt::Display for Codec {
+    /// Writes the canonical multicodec name, or the hex code of an unknown codec.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{:#x}", u64::from(*self)),
+        }
+    }
+}
+
+impl FromStr for Codec {
+    type Err = Error;
+
+    /// Parses a canonical multicodec name, or a hex code such as `0x55`.
+    fn from_str(s: &str) -> Result<Self> {
+        if let Some(codec) = Self::from_name(s) {
+            return Ok(codec);
+        }
+        match s.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16)
+                .map(Self::from_code)
+                .map_err(|_| Error::UnknownCodec),
+            None => Err(Error::UnknownCodec),
+        }
+    }
 }
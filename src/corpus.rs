@@ -0,0 +1,119 @@
+//! Reproducible corpora of CIDs, for benchmarks and golden tests.
+
+use multihash::{Code, Multihash};
+
+use crate::cid::Cid;
+use crate::codec::Codec;
+use crate::hasher::Hasher;
+
+/// The codecs of the CIDv1 of a corpus.
+const CODECS: [Codec; 6] = [
+    Codec::Raw,
+    Codec::DagProtobuf,
+    Codec::DagCBOR,
+    Codec::DagJSON,
+    Codec::Libp2pKey,
+    Codec::GitRaw,
+];
+
+/// The hash functions of the CIDv1 of a corpus, with digests of 20 to 64 bytes.
+const CODES: [Code; 8] = [
+    Code::Sha2_256,
+    Code::Sha2_512,
+    Code::Sha3_224,
+    Code::Sha3_256,
+    Code::Sha3_512,
+    Code::Blake2b256,
+    Code::Blake2b512,
+    Code::Sha1,
+];
+
+/// SplitMix64, a generator whose output only depends on the seed, on every platform.
+#[derive(Clone, Debug)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Return a number below `n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn fill(&mut self, buf: &mut [u8]) {
+        for chunk in buf.chunks_mut(8) {
+            chunk.copy_from_slice(&self.next().to_le_bytes()[..chunk.len()]);
+        }
+    }
+}
+
+fn hash(code: Code, data: &[u8]) -> Multihash {
+    let mut hasher = Hasher::new(code).expect("hash function of a known code");
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// Return an iterator over `count` CIDs generated from `seed`.
+///
+/// The corpus mixes CIDv0, CIDv1 of several codecs and hash functions, and CIDv1 with identity
+/// multihashes of 0 to 32 bytes. The same seed gives the same CIDs on every platform, and a corpus
+/// starts with the CIDs of any shorter corpus of the same seed.
+///
+/// ```
+/// let a: Vec<_> = cid::corpus(42, 100).collect();
+/// let b: Vec<_> = cid::corpus(42, 10).collect();
+/// assert_eq!(a[..10], b[..]);
+/// ```
+pub fn corpus(seed: u64, count: usize) -> Corpus {
+    Corpus {
+        rng: SplitMix64(seed),
+        remaining: count,
+    }
+}
+
+/// Iterator over a reproducible corpus of CIDs, created with [`corpus`].
+#[derive(Clone, Debug)]
+pub struct Corpus {
+    rng: SplitMix64,
+    remaining: usize,
+}
+
+impl Iterator for Corpus {
+    type Item = Cid;
+
+    fn next(&mut self) -> Option<Cid> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let kind = self.rng.below(8);
+        let mut data = [0; 32];
+        self.rng.fill(&mut data);
+        Some(match kind {
+            0 => Cid::new_v0_unchecked(hash(Code::Sha2_256, &data)),
+            1 => {
+                let codec = CODECS[self.rng.below(CODECS.len())];
+                let len = self.rng.below(data.len() + 1);
+                Cid::new_v1(codec, multihash::wrap(Code::Identity, &data[..len]))
+            }
+            _ => {
+                let codec = CODECS[self.rng.below(CODECS.len())];
+                let code = CODES[self.rng.below(CODES.len())];
+                Cid::new_v1(codec, hash(code, &data))
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Corpus {}
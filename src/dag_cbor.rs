@@ -0,0 +1,109 @@
+//! DAG-CBOR link encoding.
+//!
+//! In [DAG-CBOR](https://ipld.io/specs/codecs/dag-cbor/spec/) a CID is encoded as a byte string
+//! with CBOR tag 42. The byte string contains the binary CID, prefixed with a `0x00` byte (the
+//! identity multibase).
+//!
+//! CBOR libraries that handle tags themselves (e.g. `serde_cbor::tags::Tagged` or
+//! `ciborium::tag::Required<_, 42>`) only need the content of the byte string, see
+//! [`to_tag_payload`] and [`from_tag_payload`]. [`encode`] and [`decode`] deal with the complete
+//! CBOR data item instead.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::cid::Cid;
+use crate::error::{Error, Result};
+
+/// The CBOR tag for CIDs.
+pub const CBOR_TAG_CID: u64 = 42;
+
+/// The CBOR encoding of tag 42.
+const TAG: [u8; 2] = [0xd8, 0x2a];
+
+/// The major type of a CBOR byte string.
+const MAJOR_BYTES: u8 = 0x40;
+
+/// Return the content of the tagged byte string, the `0x00` prefixed binary CID.
+pub fn to_tag_payload(cid: &Cid) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(1 + cid.encoded_len());
+    payload.push(0x00);
+    payload.extend_from_slice(&cid.to_bytes());
+    payload
+}
+
+/// Decode a CID from the content of the tagged byte string.
+pub fn from_tag_payload(payload: &[u8]) -> Result<Cid> {
+    match payload.split_first() {
+        Some((0x00, bytes)) => Cid::try_from(bytes),
+        _ => Err(Error::InvalidDagCbor),
+    }
+}
+
+/// Encode a CID as a complete CBOR data item (tag 42 and byte string).
+pub fn encode(cid: &Cid) -> Vec<u8> {
+    let payload_len = 1 + cid.encoded_len();
+    let mut res = Vec::with_capacity(TAG.len() + 9 + payload_len);
+    res.extend_from_slice(&TAG);
+    let len = payload_len as u64;
+    if len < 24 {
+        res.push(MAJOR_BYTES | len as u8);
+    } else if len <= u64::from(u8::MAX) {
+        res.push(MAJOR_BYTES | 24);
+        res.push(len as u8);
+    } else if len <= u64::from(u16::MAX) {
+        res.push(MAJOR_BYTES | 25);
+        res.extend_from_slice(&(len as u16).to_be_bytes());
+    } else if len <= u64::from(u32::MAX) {
+        res.push(MAJOR_BYTES | 26);
+        res.extend_from_slice(&(len as u32).to_be_bytes());
+    } else {
+        res.push(MAJOR_BYTES | 27);
+        res.extend_from_slice(&len.to_be_bytes());
+    }
+    res.push(0x00);
+    res.extend_from_slice(&cid.to_bytes());
+    res
+}
+
+/// Decode a CID from a complete CBOR data item (tag 42 and byte string).
+///
+/// As required by DAG-CBOR, the byte string length must be minimally encoded. The input must not
+/// contain anything after the data item.
+pub fn decode(data: &[u8]) -> Result<Cid> {
+    if !data.starts_with(&TAG) {
+        return Err(Error::InvalidDagCbor);
+    }
+    let (&header, rest) = data[TAG.len()..]
+        .split_first()
+        .ok_or(Error::InvalidDagCbor)?;
+    if header & 0xe0 != MAJOR_BYTES {
+        return Err(Error::InvalidDagCbor);
+    }
+
+    let (len, min, payload) = match header & 0x1f {
+        len @ 0..=23 => (u64::from(len), 0, rest),
+        24 => read_len(rest, 1, 24)?,
+        25 => read_len(rest, 2, 0x100)?,
+        26 => read_len(rest, 4, 0x1_0000)?,
+        27 => read_len(rest, 8, 0x1_0000_0000)?,
+        _ => return Err(Error::InvalidDagCbor),
+    };
+    if len < min || len != payload.len() as u64 {
+        return Err(Error::InvalidDagCbor);
+    }
+    from_tag_payload(payload)
+}
+
+/// Read a big-endian length of `size` bytes, returning it with the smallest value that is
+/// minimally encoded with that size.
+fn read_len(data: &[u8], size: usize, min: u64) -> Result<(u64, u64, &[u8])> {
+    if data.len() < size {
+        return Err(Error::InvalidDagCbor);
+    }
+    let (len, rest) = data.split_at(size);
+    let len = len
+        .iter()
+        .fold(0u64, |acc, byte| (acc << 8) | u64::from(*byte));
+    Ok((len, min, rest))
+}
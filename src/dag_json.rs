@@ -0,0 +1,91 @@
+//! DAG-JSON link (de)serialization.
+//!
+//! In [DAG-JSON](https://ipld.io/specs/codecs/dag-json/spec/) a CID is represented as a map with
+//! a single `"/"` key, whose value is the string representation of the CID in base32 for CIDv1 and
+//! base58btc for CIDv0: `{"/": "bafy..."}`. CIDs parsed from other bases are written in those.
+//!
+//! This module is meant to be used with serde's `with` attribute:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Node {
+//!     #[serde(with = "cid::dag_json")]
+//!     link: Cid,
+//! }
+//! ```
+
+use alloc::string::String;
+use core::convert::TryFrom;
+use core::fmt;
+
+use serde::{de, ser};
+
+use crate::cid::Cid;
+
+/// The key of a DAG-JSON link.
+const LINK_KEY: &str = "/";
+
+/// Serialize a CID as DAG-JSON link.
+pub fn serialize<S>(cid: &Cid, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: ser::Serializer,
+{
+    use ser::SerializeMap;
+
+    let mut map = serializer.serialize_map(Some(1))?;
+    map.serialize_entry(LINK_KEY, &CidString(cid))?;
+    map.end()
+}
+
+/// Deserialize a CID from a DAG-JSON link.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Cid, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    deserializer.deserialize_map(LinkVisitor)
+}
+
+/// Serializes a CID as its canonical string, regardless of the format.
+struct CidString<'a>(&'a Cid);
+
+impl<'a> fmt::Display for CidString<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.write_canonical(f)
+    }
+}
+
+impl<'a> ser::Serialize for CidString<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Visitor to deserialize a DAG-JSON link.
+struct LinkVisitor;
+
+impl<'de> de::Visitor<'de> for LinkVisitor {
+    type Value = Cid;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("a DAG-JSON link, a map with a single \"/\" key")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        match map.next_key::<String>()? {
+            Some(ref key) if key == LINK_KEY => {}
+            Some(key) => return Err(de::Error::unknown_field(&key, &[LINK_KEY])),
+            None => return Err(de::Error::missing_field(LINK_KEY)),
+        }
+        let value: String = map.next_value()?;
+        if map.next_key::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+        Cid::try_from(value.as_str()).map_err(de::Error::custom)
+    }
+}
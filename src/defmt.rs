@@ -0,0 +1,79 @@
+//! defmt formatting.
+//!
+//! A CID is formatted by its parts, with the multihash as hex bytes, which avoids encoding it in
+//! a multibase on the device.
+
+use defmt::{Format, Formatter};
+
+use crate::cid::Cid;
+use crate::error::{Component, Error};
+
+impl Format for Cid {
+    fn format(&self, f: Formatter) {
+        defmt::write!(
+            f,
+            "Cid {{ version: {}, codec: {}, hash: {=[u8]:x} }}",
+            self.version,
+            self.codec,
+            self.hash.as_bytes()
+        )
+    }
+}
+
+impl Format for Error {
+    /// Writes the same messages as `Display`, without the underlying multibase, multihash and
+    /// varint errors, which don't implement `Format`.
+    fn format(&self, f: Formatter) {
+        use self::Error::*;
+        match self {
+            UnknownCodec => defmt::write!(f, "Unknown codec"),
+            InputTooShort => defmt::write!(f, "Input too short"),
+            InputTooLong => defmt::write!(f, "Input too long"),
+            Multibase(_) => defmt::write!(f, "Failed to decode multibase"),
+            Multihash(_) => defmt::write!(f, "Failed to parse multihash"),
+            MultihashEncode(_) => defmt::write!(f, "Failed to encode multihash"),
+            Malformed {
+                component: Component::Multibase,
+                offset,
+                ..
+            } => defmt::write!(f, "Invalid multibase encoding at character {}", offset),
+            Malformed {
+                component, offset, ..
+            } => defmt::write!(f, "Invalid {} at byte {}", component, offset),
+            InvalidCidVersion => defmt::write!(f, "Unrecognized CID version"),
+            UnsupportedVersion(version) => defmt::write!(f, "Unsupported CID version {}", version),
+            InvalidCidV0Codec => defmt::write!(f, "CIDv0 requires a DagPB codec"),
+            InvalidCidV0Multihash => defmt::write!(f, "CIDv0 requires a Sha-256 multihash"),
+            InvalidCidV0Base => defmt::write!(f, "CIDv0 requires a Base58Btc base"),
+            VarInt(_) => defmt::write!(f, "Failed to decode unsigned varint format"),
+            InvalidDagCbor => defmt::write!(f, "Invalid DAG-CBOR encoded CID"),
+            InvalidBson => defmt::write!(f, "BSON value must be generic binary or a string"),
+            DigestTooLarge => defmt::write!(f, "Multihash digest too large"),
+            UnsupportedMultihash => defmt::write!(f, "No hash function for the multihash code"),
+            InvalidPeerId => defmt::write!(
+                f,
+                "Not a libp2p-key CID with an identity or Sha-256 multihash"
+            ),
+            InlineDataTooLarge => defmt::write!(f, "Data too large to inline in the CID"),
+            NonMinimalVarint => defmt::write!(f, "Unsigned varint is not minimally encoded"),
+            TrailingBytes => defmt::write!(f, "Trailing bytes after the CID"),
+            PaddedMultibase => defmt::write!(f, "Padded multibase encodings are not allowed"),
+            InvalidCidV0String => defmt::write!(
+                f,
+                "CIDv0 strings must be 46 base58btc characters starting with Qm"
+            ),
+            DigestMismatch(mismatch) => defmt::write!(
+                f,
+                "Data does not match the CID digest: expected {=[u8]:x}, got {=[u8]:x}",
+                mismatch.expected.digest(),
+                mismatch.actual.digest()
+            ),
+            InvalidPath => defmt::write!(f, "Not an /ipfs/ or /ipns/ path"),
+            InvalidUrl => defmt::write!(f, "Not an IPFS gateway URL"),
+            InvalidDnsLink => defmt::write!(f, "Not a DNSLink value with an /ipfs/ or /ipns/ path"),
+            InvalidDatastoreKey => defmt::write!(f, "Not a datastore key of a block"),
+            InvalidIndex => defmt::write!(f, "Invalid or corrupted CID index"),
+            InvalidBloomFilter => defmt::write!(f, "Invalid or corrupted CID Bloom filter"),
+        }
+    }
+}
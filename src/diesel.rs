@@ -0,0 +1,73 @@
+//! Diesel integration.
+//!
+//! A CID can be stored in a binary column (its encoded bytes) or in a text column (its string
+//! representation). SQLite needs the `diesel-sqlite` feature in addition to `diesel`.
+
+use core::convert::TryFrom;
+use std::io::Write;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::query_builder::bind_collector::RawBytesBindCollector;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::{Binary, Text};
+
+use crate::cid::Cid;
+
+impl<DB> ToSql<Binary, DB> for Cid
+where
+    for<'c> DB: Backend<BindCollector<'c> = RawBytesBindCollector<DB>>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        self.write_bytes(out)?;
+        Ok(IsNull::No)
+    }
+}
+
+impl<DB> ToSql<Text, DB> for Cid
+where
+    for<'c> DB: Backend<BindCollector<'c> = RawBytesBindCollector<DB>>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        write!(out, "{}", self)?;
+        Ok(IsNull::No)
+    }
+}
+
+#[cfg(feature = "diesel-sqlite")]
+impl ToSql<Binary, diesel::sqlite::Sqlite> for Cid {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::sqlite::Sqlite>) -> serialize::Result {
+        out.set_value(self.to_bytes());
+        Ok(IsNull::No)
+    }
+}
+
+#[cfg(feature = "diesel-sqlite")]
+impl ToSql<Text, diesel::sqlite::Sqlite> for Cid {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::sqlite::Sqlite>) -> serialize::Result {
+        out.set_value(self.to_string());
+        Ok(IsNull::No)
+    }
+}
+
+impl<DB> FromSql<Binary, DB> for Cid
+where
+    DB: Backend,
+    Vec<u8>: FromSql<Binary, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        let bytes = Vec::<u8>::from_sql(bytes)?;
+        Ok(Cid::try_from(bytes)?)
+    }
+}
+
+impl<DB> FromSql<Text, DB> for Cid
+where
+    DB: Backend,
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        let string = String::from_sql(bytes)?;
+        Ok(Cid::try_from(string)?)
+    }
+}
@@ -0,0 +1,53 @@
+//! Datastore keys of blocks, as used by kubo (go-ipfs).
+//!
+//! Kubo's `dshelp` package keys blocks by their multihash only, encoded as unpadded uppercase
+//! base32 without a multibase prefix, e.g. `/CIQ...`. The blockstore stores them under the
+//! [`BLOCKS_PREFIX`] namespace, e.g. `/blocks/CIQ...`, and flatfs uses the key without its slash
+//! as the file name. The codec isn't part of the key, so it has to be given to get a CID back.
+
+use alloc::format;
+use alloc::string::String;
+
+use multibase::Base;
+use multihash::Multihash;
+
+use crate::cid::Cid;
+use crate::codec::Codec;
+use crate::error::{Error, Result};
+
+/// The namespace of the blockstore in kubo's datastore.
+pub const BLOCKS_PREFIX: &str = "/blocks";
+
+/// Return the datastore key of a multihash, e.g. `/CIQ...`.
+pub fn multihash_to_ds_key(hash: &Multihash) -> String {
+    format!("/{}", Base::Base32Upper.encode(hash.as_bytes()))
+}
+
+/// Return the datastore key of the multihash of a CID, e.g. `/CIQ...`.
+pub fn cid_to_ds_key(cid: &Cid) -> String {
+    multihash_to_ds_key(&cid.hash)
+}
+
+/// Return the key of a CID in kubo's blockstore, e.g. `/blocks/CIQ...`.
+pub fn cid_to_block_key(cid: &Cid) -> String {
+    format!("{}{}", BLOCKS_PREFIX, cid_to_ds_key(cid))
+}
+
+/// Decode the multihash of a datastore key, with or without the [`BLOCKS_PREFIX`].
+///
+/// Returns `Error::InvalidDatastoreKey` if the key doesn't start with a `/` or has more
+/// segments.
+pub fn ds_key_to_multihash(key: &str) -> Result<Multihash> {
+    let key = key.strip_prefix(BLOCKS_PREFIX).unwrap_or(key);
+    let encoded = key
+        .strip_prefix('/')
+        .filter(|encoded| !encoded.contains('/'))
+        .ok_or(Error::InvalidDatastoreKey)?;
+    let bytes = Base::Base32Upper.decode(encoded)?;
+    Ok(Multihash::from_bytes(bytes)?)
+}
+
+/// Decode a datastore key into a CIDv1 with the given codec, e.g. [`Codec::Raw`] as kubo does.
+pub fn ds_key_to_cid(key: &str, codec: Codec) -> Result<Cid> {
+    Ok(Cid::new_v1(codec, ds_key_to_multihash(key)?))
+}
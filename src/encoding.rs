@@ -0,0 +1,210 @@
+//! Streaming multibase encoders, used to display a CID without allocating.
+
+use core::fmt::{self, Write};
+
+use multibase::Base;
+
+/// Size of the scratch space for the base-x (base10, base36, base58) encoders. Inputs whose
+/// encoding doesn't fit fall back to the allocating `multibase` encoder.
+const BASE_X_DIGITS: usize = 256;
+
+/// Write `data` encoded in the given base, without the multibase prefix.
+///
+/// The input is passed as several slices, which are encoded as if they were concatenated, so that
+/// the binary representation of a CID doesn't need to be assembled first.
+pub(crate) fn write_base<W: Write + ?Sized>(w: &mut W, base: Base, data: &[&[u8]]) -> fmt::Result {
+    let bytes = || data.iter().flat_map(|part| part.iter().copied());
+    match base {
+        Base::Base2 => write_bits(w, bytes(), b"01", 1, false),
+        Base::Base8 => write_bits(w, bytes(), b"01234567", 3, false),
+        Base::Base10 => write_base_x(w, base, data, b"0123456789"),
+        Base::Base16Lower => write_bits(w, bytes(), b"0123456789abcdef", 4, false),
+        Base::Base16Upper => write_bits(w, bytes(), b"0123456789ABCDEF", 4, false),
+        Base::Base32Lower => write_bits(w, bytes(), BASE32_LOWER, 5, false),
+        Base::Base32Upper => write_bits(w, bytes(), BASE32_UPPER, 5, false),
+        Base::Base32PadLower => write_bits(w, bytes(), BASE32_LOWER, 5, true),
+        Base::Base32PadUpper => write_bits(w, bytes(), BASE32_UPPER, 5, true),
+        Base::Base32HexLower => write_bits(w, bytes(), BASE32HEX_LOWER, 5, false),
+        Base::Base32HexUpper => write_bits(w, bytes(), BASE32HEX_UPPER, 5, false),
+        Base::Base32HexPadLower => write_bits(w, bytes(), BASE32HEX_LOWER, 5, true),
+        Base::Base32HexPadUpper => write_bits(w, bytes(), BASE32HEX_UPPER, 5, true),
+        Base::Base32Z => write_bits(w, bytes(), b"ybndrfg8ejkmcpqxot1uwisza345h769", 5, false),
+        Base::Base36Lower => write_base_x(w, base, data, b"0123456789abcdefghijklmnopqrstuvwxyz"),
+        Base::Base36Upper => write_base_x(w, base, data, b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+        Base::Base58Flickr => write_base_x(w, base, data, BASE58_FLICKR),
+        Base::Base58Btc => write_base_x(w, base, data, BASE58_BITCOIN),
+        Base::Base64 => write_bits(w, bytes(), BASE64, 6, false),
+        Base::Base64Pad => write_bits(w, bytes(), BASE64, 6, true),
+        Base::Base64Url => write_bits(w, bytes(), BASE64URL, 6, false),
+        Base::Base64UrlPad => write_bits(w, bytes(), BASE64URL, 6, true),
+        // Identity, which isn't really suitable for display anyway.
+        _ => write_alloc(w, base, data),
+    }
+}
+
+/// Write the output of `write` padded to the width of the formatter, with its fill and alignment,
+/// like `Formatter::pad` does for a `str`. Precision is ignored.
+///
+/// Without a width the output is streamed to the formatter. Otherwise it is written twice, once
+/// to count its characters, so that it doesn't need to be buffered.
+pub(crate) fn pad<F>(f: &mut fmt::Formatter, write: F) -> fmt::Result
+where
+    F: Fn(&mut dyn Write) -> fmt::Result,
+{
+    let width = match f.width() {
+        Some(width) => width,
+        None => return write(f),
+    };
+    let mut counter = CharCounter(0);
+    write(&mut counter)?;
+    let padding = width.saturating_sub(counter.0);
+    // Left aligned by default, like strings.
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    write(f)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+/// Whether the output of `write` is exactly `expected`, without buffering it.
+///
+/// The output is compared as it is written, and writing stops at the first difference.
+pub(crate) fn matches<F>(expected: &str, write: F) -> bool
+where
+    F: FnOnce(&mut dyn Write) -> fmt::Result,
+{
+    let mut matcher = Matcher(expected);
+    write(&mut matcher).is_ok() && matcher.0.is_empty()
+}
+
+/// Writer that fails as soon as the output differs from the rest of the expected string.
+struct Matcher<'a>(&'a str);
+
+impl Write for Matcher<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self.0.strip_prefix(s) {
+            Some(rest) => {
+                self.0 = rest;
+                Ok(())
+            }
+            None => Err(fmt::Error),
+        }
+    }
+}
+
+/// Writer that only counts the characters written to it.
+struct CharCounter(usize);
+
+impl Write for CharCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+const BASE32_LOWER: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE32_UPPER: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE32HEX_LOWER: &[u8] = b"0123456789abcdefghijklmnopqrstuv";
+const BASE32HEX_UPPER: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+const BASE58_FLICKR: &[u8] = b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ";
+const BASE58_BITCOIN: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64URL: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encode with a power of two base, `bits` bits per symbol, most significant bit first (like
+/// RFC 4648).
+fn write_bits<W: Write + ?Sized>(
+    w: &mut W,
+    bytes: impl Iterator<Item = u8>,
+    alphabet: &[u8],
+    bits: u32,
+    pad: bool,
+) -> fmt::Result {
+    let mask = (1u16 << bits) - 1;
+    let mut acc: u16 = 0;
+    let mut acc_bits = 0;
+    let mut written = 0;
+    for byte in bytes {
+        acc = (acc << 8) | u16::from(byte);
+        acc_bits += 8;
+        while acc_bits >= bits {
+            acc_bits -= bits;
+            w.write_char(alphabet[usize::from((acc >> acc_bits) & mask)] as char)?;
+            written += 1;
+        }
+    }
+    if acc_bits > 0 {
+        w.write_char(alphabet[usize::from((acc << (bits - acc_bits)) & mask)] as char)?;
+        written += 1;
+    }
+    if pad {
+        // A padded block is the smallest number of symbols that is a whole number of bytes.
+        let block = match bits {
+            5 => 8,
+            6 => 4,
+            _ => 1,
+        };
+        while written % block != 0 {
+            w.write_char('=')?;
+            written += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Encode with an arbitrary base by repeated division (like the `base-x` crate).
+fn write_base_x<W: Write + ?Sized>(
+    w: &mut W,
+    base: Base,
+    data: &[&[u8]],
+    alphabet: &[u8],
+) -> fmt::Result {
+    let radix = alphabet.len() as u32;
+    let bytes = data.iter().flat_map(|part| part.iter().copied());
+
+    // Leading zero bytes are encoded as leading zero symbols.
+    let zeros = bytes.clone().take_while(|byte| *byte == 0).count();
+
+    // Little-endian digits of the remaining number.
+    let mut digits = [0u8; BASE_X_DIGITS];
+    let mut len = 0;
+    for byte in bytes.skip(zeros) {
+        let mut carry = u32::from(byte);
+        for digit in &mut digits[..len] {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % radix) as u8;
+            carry /= radix;
+        }
+        while carry > 0 {
+            if len == BASE_X_DIGITS {
+                return write_alloc(w, base, data);
+            }
+            digits[len] = (carry % radix) as u8;
+            len += 1;
+            carry /= radix;
+        }
+    }
+
+    for _ in 0..zeros {
+        w.write_char(alphabet[0] as char)?;
+    }
+    for digit in digits[..len].iter().rev() {
+        w.write_char(alphabet[usize::from(*digit)] as char)?;
+    }
+    Ok(())
+}
+
+/// Fallback for inputs that are too large to be encoded on the stack, or bases that have no
+/// streaming encoder.
+fn write_alloc<W: Write + ?Sized>(w: &mut W, base: Base, data: &[&[u8]]) -> fmt::Result {
+    w.write_str(&base.encode(data.concat()))
+}
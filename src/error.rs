@@ -1,40 +1,279 @@
-use std::{error, fmt};
+use alloc::boxed::Box;
+use core::fmt;
+
+use multihash::Multihash;
 
 /// Type alias to use this library's [`Error`] type in a `Result`.
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
 
-/// Error types
+/// The part of a CID that failed to parse.
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Component {
+    /// The multibase encoding of a string.
+    Multibase,
+    /// The version varint.
+    Version,
+    /// The codec varint.
+    Codec,
+    /// The multihash.
+    Multihash,
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Component::Multibase => "multibase encoding",
+            Component::Version => "version varint",
+            Component::Codec => "codec varint",
+            Component::Multihash => "multihash",
+        })
+    }
+}
+
+/// The error of the decoder that a part of a CID failed in.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[non_exhaustive]
+pub enum Cause {
+    /// Multibase decoding failure.
+    Multibase(multibase::Error),
+    /// Multihash decoding failure.
+    Multihash(multihash::DecodeError),
+    /// Varint decoding failure.
+    VarInt(unsigned_varint::decode::Error),
+}
+
+impl fmt::Display for Cause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Cause::Multibase(err) => fmt::Display::fmt(err, f),
+            Cause::Multihash(err) => fmt::Display::fmt(err, f),
+            Cause::VarInt(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+/// The digest of a CID and the digest of the data checked against it.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Mismatch {
+    /// The multihash of the CID.
+    pub expected: Multihash,
+    /// The multihash of the data, truncated to the length of the expected digest.
+    pub actual: Multihash,
+}
+
+/// Write the digest as lowercase hex.
+fn write_hex(f: &mut fmt::Formatter, hash: &Multihash) -> fmt::Result {
+    hash.digest()
+        .iter()
+        .try_for_each(|byte| write!(f, "{:02x}", byte))
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("expected ")?;
+        write_hex(f, &self.expected)?;
+        f.write_str(", got ")?;
+        write_hex(f, &self.actual)
+    }
+}
+
+/// Error types
+///
+/// New variants may be added, so match on the categories of [`Error::is_parse`] and
+/// [`Error::is_unsupported`] rather than listing them all.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Error), uniffi(flat_error))]
+#[non_exhaustive]
 pub enum Error {
     /// Unknown CID codec.
     UnknownCodec,
     /// Input data is too short.
     InputTooShort,
-    /// Multibase or multihash codec failure
-    ParsingError,
+    /// Input data is longer than the parsing limits allow.
+    InputTooLong,
+    /// Multibase decoding failure.
+    Multibase(multibase::Error),
+    /// Multihash decoding failure.
+    Multihash(multihash::DecodeError),
+    /// Multihash encoding failure.
+    MultihashEncode(multihash::EncodeError),
+    /// A part of the CID that couldn't be parsed.
+    Malformed {
+        /// The part that couldn't be parsed.
+        component: Component,
+        /// For the multibase encoding, the position of the first invalid character in the string.
+        /// Otherwise, the byte offset of the invalid field in the binary CID.
+        offset: usize,
+        /// The error of the underlying decoder, if it failed. Also returned by
+        /// `std::error::Error::source`.
+        source: Option<Cause>,
+    },
     /// Invalid CID version.
     InvalidCidVersion,
+    /// CID version that is reserved or not supported yet, with its number.
+    UnsupportedVersion(u64),
     /// Invalid CIDv0 codec.
     InvalidCidV0Codec,
     /// Invalid CIDv0 multihash.
     InvalidCidV0Multihash,
+    /// Invalid CIDv0 base encoding.
+    InvalidCidV0Base,
     /// Varint decode failure.
-    VarIntDecodeError,
+    VarInt(unsigned_varint::decode::Error),
+    /// Invalid DAG-CBOR encoded CID.
+    InvalidDagCbor,
+    /// BSON value that is neither generic binary nor a string.
+    InvalidBson,
+    /// Multihash digest is larger than the inline storage or the parsing limits allow.
+    DigestTooLarge,
+    /// No hash function is available for the multihash code.
+    UnsupportedMultihash,
+    /// Not a libp2p-key CID with an identity or SHA2-256 multihash.
+    InvalidPeerId,
+    /// Data is too large to be inlined in an identity multihash.
+    InlineDataTooLarge,
+    /// Unsigned varint that isn't minimally encoded.
+    NonMinimalVarint,
+    /// Input data after the end of the CID.
+    TrailingBytes,
+    /// Multibase encoding with padding.
+    PaddedMultibase,
+    /// CIDv0 string that isn't 46 characters of base58btc starting with "Qm".
+    InvalidCidV0String,
+    /// Data whose digest doesn't match the CID, with both digests.
+    DigestMismatch(Box<Mismatch>),
+    /// Not an `/ipfs/<cid>` or `/ipns/<name>` path.
+    InvalidPath,
+    /// Not an IPFS gateway URL.
+    InvalidUrl,
+    /// Not a `dnslink=` TXT record value with an `/ipfs/` or `/ipns/` path.
+    InvalidDnsLink,
+    /// Not a datastore key of a block.
+    InvalidDatastoreKey,
+    /// Not a serialized CID index, or a corrupted one.
+    InvalidIndex,
+    /// Not a serialized CID Bloom filter, or a corrupted one.
+    InvalidBloomFilter,
 }
 
-impl error::Error for Error {}
+impl Error {
+    /// Whether the input isn't a valid CID, or not in the expected encoding.
+    pub fn is_parse(&self) -> bool {
+        use self::Error::*;
+        matches!(
+            self,
+            InputTooShort
+                | InputTooLong
+                | Multibase(_)
+                | Multihash(_)
+                | Malformed { .. }
+                | InvalidCidVersion
+                | InvalidCidV0Codec
+                | InvalidCidV0Multihash
+                | InvalidCidV0Base
+                | VarInt(_)
+                | InvalidDagCbor
+                | InvalidBson
+                | DigestTooLarge
+                | NonMinimalVarint
+                | TrailingBytes
+                | PaddedMultibase
+                | InvalidCidV0String
+                | InvalidPath
+                | InvalidUrl
+                | InvalidDnsLink
+                | InvalidDatastoreKey
+                | InvalidIndex
+                | InvalidBloomFilter
+        )
+    }
+
+    /// Whether the input is well-formed, but uses a codec, version or multihash that isn't
+    /// supported.
+    pub fn is_unsupported(&self) -> bool {
+        matches!(
+            self,
+            Error::UnknownCodec
+                | Error::UnsupportedVersion(_)
+                | Error::UnsupportedMultihash
+                | Error::MultihashEncode(_)
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Multibase(err) => Some(err),
+            Error::Multihash(err) => Some(err),
+            Error::MultihashEncode(err) => Some(err),
+            Error::VarInt(err) => Some(err),
+            Error::Malformed {
+                source: Some(cause),
+                ..
+            } => Some(match cause {
+                Cause::Multibase(err) => err,
+                Cause::Multihash(err) => err,
+                Cause::VarInt(err) => err,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    /// Convert to an `InvalidData` I/O error, which keeps the `Error` as its inner error.
+    fn from(err: Error) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+    }
+}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::Error::*;
-        let error = match *self {
+        let error = match self {
             UnknownCodec => "Unknown codec",
             InputTooShort => "Input too short",
-            ParsingError => "Failed to parse multihash",
+            InputTooLong => "Input too long",
+            Multibase(_) => "Failed to decode multibase",
+            Multihash(_) => "Failed to parse multihash",
+            MultihashEncode(_) => "Failed to encode multihash",
+            Malformed {
+                component: Component::Multibase,
+                offset,
+                ..
+            } => return write!(f, "Invalid multibase encoding at character {}", offset),
+            Malformed {
+                component, offset, ..
+            } => return write!(f, "Invalid {} at byte {}", component, offset),
             InvalidCidVersion => "Unrecognized CID version",
+            UnsupportedVersion(version) => return write!(f, "Unsupported CID version {}", version),
             InvalidCidV0Codec => "CIDv0 requires a DagPB codec",
             InvalidCidV0Multihash => "CIDv0 requires a Sha-256 multihash",
-            VarIntDecodeError => "Failed to decode unsigned varint format",
+            InvalidCidV0Base => "CIDv0 requires a Base58Btc base",
+            VarInt(_) => "Failed to decode unsigned varint format",
+            InvalidDagCbor => "Invalid DAG-CBOR encoded CID",
+            InvalidBson => "BSON value must be generic binary or a string",
+            DigestTooLarge => "Multihash digest too large",
+            UnsupportedMultihash => "No hash function for the multihash code",
+            InvalidPeerId => "Not a libp2p-key CID with an identity or Sha-256 multihash",
+            InlineDataTooLarge => "Data too large to inline in the CID",
+            NonMinimalVarint => "Unsigned varint is not minimally encoded",
+            TrailingBytes => "Trailing bytes after the CID",
+            PaddedMultibase => "Padded multibase encodings are not allowed",
+            InvalidCidV0String => "CIDv0 strings must be 46 base58btc characters starting with Qm",
+            DigestMismatch(mismatch) => {
+                return write!(f, "Data does not match the CID digest: {}", mismatch)
+            }
+            InvalidPath => "Not an /ipfs/ or /ipns/ path",
+            InvalidUrl => "Not an IPFS gateway URL",
+            InvalidDnsLink => "Not a DNSLink value with an /ipfs/ or /ipns/ path",
+            InvalidDatastoreKey => "Not a datastore key of a block",
+            InvalidIndex => "Invalid or corrupted CID index",
+            InvalidBloomFilter => "Invalid or corrupted CID Bloom filter",
         };
 
         f.write_str(error)
@@ -42,31 +281,31 @@ impl fmt::Display for Error {
 }
 
 impl From<multibase::Error> for Error {
-    fn from(_: multibase::Error) -> Error {
-        Error::ParsingError
+    fn from(err: multibase::Error) -> Error {
+        Error::Multibase(err)
     }
 }
 
 impl From<multihash::EncodeError> for Error {
-    fn from(_: multihash::EncodeError) -> Error {
-        Error::ParsingError
+    fn from(err: multihash::EncodeError) -> Error {
+        Error::MultihashEncode(err)
     }
 }
 
 impl From<multihash::DecodeError> for Error {
-    fn from(_: multihash::DecodeError) -> Error {
-        Error::ParsingError
+    fn from(err: multihash::DecodeError) -> Error {
+        Error::Multihash(err)
     }
 }
 
 impl From<multihash::DecodeOwnedError> for Error {
-    fn from(_: multihash::DecodeOwnedError) -> Error {
-        Error::ParsingError
+    fn from(err: multihash::DecodeOwnedError) -> Error {
+        Error::Multihash(err.error)
     }
 }
 
 impl From<unsigned_varint::decode::Error> for Error {
-    fn from(_: unsigned_varint::decode::Error) -> Self {
-        Error::VarIntDecodeError
+    fn from(err: unsigned_varint::decode::Error) -> Self {
+        Error::VarInt(err)
     }
 }
@@ -0,0 +1,296 @@
+//! C FFI.
+//!
+//! CIDs are passed to C as opaque [`CidHandle`] pointers, created by [`cid_parse`] or
+//! [`cid_from_bytes`] and released with [`cid_free`]. Functions that can fail return a
+//! [`CidError`] code, with `CID_OK` (0) meaning success.
+//!
+//! Strings and byte buffers returned by this module are owned by the caller and must be released
+//! with [`cid_string_free`] and [`cid_bytes_free`] respectively.
+//!
+//! To link against it, build the crate as static or dynamic library with the `ffi` feature, e.g.
+//! `cargo rustc --release --features ffi --crate-type staticlib`, and include `include/cid.h`.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::{ptr, slice};
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+
+use crate::cid::Cid;
+use crate::error::{Component, Error};
+
+/// An owned CID, opaque to C.
+#[repr(C)]
+pub struct CidHandle {
+    cid: Cid,
+}
+
+/// Error codes returned by the FFI functions.
+#[repr(C)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum CidError {
+    /// Success.
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// The input string is not valid UTF-8.
+    InvalidUtf8 = 2,
+    /// Unknown CID codec.
+    UnknownCodec = 3,
+    /// Input data is too short.
+    InputTooShort = 4,
+    /// Multibase or multihash codec failure.
+    ParsingError = 5,
+    /// Invalid CID version.
+    InvalidCidVersion = 6,
+    /// Invalid CIDv0 codec.
+    InvalidCidV0Codec = 7,
+    /// Invalid CIDv0 multihash.
+    InvalidCidV0Multihash = 8,
+    /// Invalid CIDv0 base encoding.
+    InvalidCidV0Base = 9,
+    /// Varint decode failure.
+    VarIntDecodeError = 10,
+    /// Input data is longer than the parsing limits allow.
+    InputTooLong = 11,
+    /// Multihash digest is larger than the parsing limits allow.
+    DigestTooLarge = 12,
+    /// Any other error.
+    Other = 255,
+}
+
+impl CidError {
+    /// Convert a code received from C, which may not be a declared one.
+    fn from_code(code: c_int) -> Option<Self> {
+        Some(match code {
+            0 => CidError::Ok,
+            1 => CidError::NullPointer,
+            2 => CidError::InvalidUtf8,
+            3 => CidError::UnknownCodec,
+            4 => CidError::InputTooShort,
+            5 => CidError::ParsingError,
+            6 => CidError::InvalidCidVersion,
+            7 => CidError::InvalidCidV0Codec,
+            8 => CidError::InvalidCidV0Multihash,
+            9 => CidError::InvalidCidV0Base,
+            10 => CidError::VarIntDecodeError,
+            11 => CidError::InputTooLong,
+            12 => CidError::DigestTooLarge,
+            255 => CidError::Other,
+            _ => return None,
+        })
+    }
+}
+
+impl From<Error> for CidError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::UnknownCodec => CidError::UnknownCodec,
+            Error::InputTooShort => CidError::InputTooShort,
+            Error::InputTooLong => CidError::InputTooLong,
+            Error::DigestTooLarge => CidError::DigestTooLarge,
+            Error::Multibase(_)
+            | Error::Multihash(_)
+            | Error::Malformed {
+                component: Component::Multibase,
+                ..
+            }
+            | Error::Malformed {
+                component: Component::Multihash,
+                ..
+            } => CidError::ParsingError,
+            Error::InvalidCidVersion | Error::UnsupportedVersion(_) => CidError::InvalidCidVersion,
+            Error::InvalidCidV0Codec => CidError::InvalidCidV0Codec,
+            Error::InvalidCidV0Multihash => CidError::InvalidCidV0Multihash,
+            Error::InvalidCidV0Base => CidError::InvalidCidV0Base,
+            Error::VarInt(_) | Error::Malformed { .. } => CidError::VarIntDecodeError,
+            _ => CidError::Other,
+        }
+    }
+}
+
+/// Store `result` in `out`, returning the matching error code.
+unsafe fn put(result: Result<Cid, Error>, out: *mut *mut CidHandle) -> CidError {
+    match result {
+        Ok(cid) => {
+            *out = Box::into_raw(Box::new(CidHandle { cid }));
+            CidError::Ok
+        }
+        Err(err) => err.into(),
+    }
+}
+
+/// Parse a CID from a NUL-terminated string.
+///
+/// On success the new handle is written to `out`.
+///
+/// # Safety
+///
+/// `input` must be null or a valid NUL-terminated string, `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn cid_parse(input: *const c_char, out: *mut *mut CidHandle) -> CidError {
+    if input.is_null() || out.is_null() {
+        return CidError::NullPointer;
+    }
+    match CStr::from_ptr(input).to_str() {
+        Ok(input) => put(Cid::try_from(input), out),
+        Err(_) => CidError::InvalidUtf8,
+    }
+}
+
+/// Decode a CID from `len` bytes at `data`.
+///
+/// On success the new handle is written to `out`.
+///
+/// # Safety
+///
+/// `data` must be null or valid for reads of `len` bytes, `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn cid_from_bytes(
+    data: *const u8,
+    len: usize,
+    out: *mut *mut CidHandle,
+) -> CidError {
+    if data.is_null() || out.is_null() {
+        return CidError::NullPointer;
+    }
+    put(Cid::try_from(slice::from_raw_parts(data, len)), out)
+}
+
+/// Return the string representation of a CID, or null if `cid` is null.
+///
+/// The string must be released with [`cid_string_free`].
+///
+/// # Safety
+///
+/// `cid` must be null or a handle that wasn't freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn cid_to_string(cid: *const CidHandle) -> *mut c_char {
+    match cid.as_ref() {
+        // A CID string never contains a NUL byte.
+        Some(handle) => {
+            CString::new(handle.cid.to_string()).map_or(ptr::null_mut(), CString::into_raw)
+        }
+        None => ptr::null_mut(),
+    }
+}
+
+/// Return the encoded bytes of a CID and write their length to `len`, or null if an argument is
+/// null.
+///
+/// The buffer must be released with [`cid_bytes_free`].
+///
+/// # Safety
+///
+/// `cid` must be null or a handle that wasn't freed yet, `len` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn cid_to_bytes(cid: *const CidHandle, len: *mut usize) -> *mut u8 {
+    match cid.as_ref() {
+        Some(handle) if !len.is_null() => {
+            let bytes = handle.cid.to_bytes().into_boxed_slice();
+            *len = bytes.len();
+            Box::into_raw(bytes) as *mut u8
+        }
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Return the version of a CID, or `u64::MAX` if `cid` is null.
+///
+/// # Safety
+///
+/// `cid` must be null or a handle that wasn't freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn cid_version(cid: *const CidHandle) -> u64 {
+    cid.as_ref()
+        .map_or(u64::MAX, |handle| handle.cid.version.into())
+}
+
+/// Return the multicodec code of a CID, or `u64::MAX` if `cid` is null.
+///
+/// # Safety
+///
+/// `cid` must be null or a handle that wasn't freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn cid_codec(cid: *const CidHandle) -> u64 {
+    cid.as_ref()
+        .map_or(u64::MAX, |handle| handle.cid.codec.into())
+}
+
+/// Whether two CIDs are equal. Null handles are only equal to each other.
+///
+/// # Safety
+///
+/// Both arguments must be null or handles that weren't freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn cid_equals(a: *const CidHandle, b: *const CidHandle) -> bool {
+    match (a.as_ref(), b.as_ref()) {
+        (Some(a), Some(b)) => a.cid == b.cid,
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Release a CID handle. Does nothing if `cid` is null.
+///
+/// # Safety
+///
+/// `cid` must be null or a handle that wasn't freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn cid_free(cid: *mut CidHandle) {
+    if !cid.is_null() {
+        drop(Box::from_raw(cid));
+    }
+}
+
+/// Release a string returned by [`cid_to_string`]. Does nothing if `string` is null.
+///
+/// # Safety
+///
+/// `string` must be null or a string returned by [`cid_to_string`] that wasn't freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn cid_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Release a buffer returned by [`cid_to_bytes`]. Does nothing if `data` is null.
+///
+/// # Safety
+///
+/// `data` and `len` must be a buffer and its length returned by [`cid_to_bytes`] that wasn't
+/// freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn cid_bytes_free(data: *mut u8, len: usize) {
+    if !data.is_null() {
+        drop(Vec::from_raw_parts(data, len, len));
+    }
+}
+
+/// Return a static, NUL-terminated description of an error code.
+///
+/// Takes the code as a C `int`, so any value is safe to pass. Values that aren't a [`CidError`]
+/// get a generic description.
+#[no_mangle]
+pub extern "C" fn cid_error_message(err: c_int) -> *const c_char {
+    let message: &'static [u8] = match CidError::from_code(err) {
+        Some(CidError::Ok) => b"Success\0",
+        Some(CidError::NullPointer) => b"Null pointer argument\0",
+        Some(CidError::InvalidUtf8) => b"Input is not valid UTF-8\0",
+        Some(CidError::UnknownCodec) => b"Unknown codec\0",
+        Some(CidError::InputTooShort) => b"Input too short\0",
+        Some(CidError::ParsingError) => b"Failed to parse multihash\0",
+        Some(CidError::InvalidCidVersion) => b"Unrecognized CID version\0",
+        Some(CidError::InvalidCidV0Codec) => b"CIDv0 requires a DagPB codec\0",
+        Some(CidError::InvalidCidV0Multihash) => b"CIDv0 requires a Sha-256 multihash\0",
+        Some(CidError::InvalidCidV0Base) => b"CIDv0 requires a Base58Btc base\0",
+        Some(CidError::VarIntDecodeError) => b"Failed to decode unsigned varint format\0",
+        Some(CidError::InputTooLong) => b"Input too long\0",
+        Some(CidError::DigestTooLarge) => b"Multihash digest too large\0",
+        Some(CidError::Other) => b"Invalid CID\0",
+        None => b"Unknown error\0",
+    };
+    message.as_ptr() as *const c_char
+}
@@ -0,0 +1,82 @@
+//! Incremental decoding of a CID from a stream, shared by the sync and async readers.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use multihash::Code;
+use unsigned_varint::{decode as varint_decode, encode as varint_encode};
+
+use crate::cid::Cid;
+use crate::error::{Error, Result};
+use crate::limits::Limits;
+
+/// What a [`CidReader`] needs next.
+pub(crate) enum Step {
+    /// A single byte of a varint.
+    Byte,
+    /// The digest of the multihash, the last part of the CID.
+    Digest,
+}
+
+/// Decoder that is fed a CID byte by byte, until only the digest is left.
+///
+/// The varints are read one byte at a time, so that no byte after the CID is consumed.
+pub(crate) struct CidReader {
+    bytes: Vec<u8>,
+    /// The start of the varint being read.
+    start: usize,
+    /// The number of varints left to read: version, codec, multihash code and length.
+    varints: usize,
+}
+
+impl CidReader {
+    pub(crate) fn new() -> Self {
+        Self {
+            bytes: Vec::with_capacity(64),
+            start: 0,
+            varints: 4,
+        }
+    }
+
+    pub(crate) fn step(&self) -> Step {
+        match self.varints {
+            0 => Step::Digest,
+            _ => Step::Byte,
+        }
+    }
+
+    /// Add a byte of the varint being read.
+    pub(crate) fn push(&mut self, byte: u8) -> Result<()> {
+        self.bytes.push(byte);
+        let len = self.bytes.len() - self.start;
+        if !varint_decode::is_last(byte) && len < varint_encode::u64_buffer().len() {
+            return Ok(());
+        }
+
+        let (value, _) = varint_decode::u64(&self.bytes[self.start..])?;
+        self.start = self.bytes.len();
+        self.varints -= 1;
+        // A CIDv0 is a bare SHA2-256 multihash, whose code is read as the version, so only its
+        // length is left. It is told apart when parsing the bytes.
+        if self.varints == 3 && value == Code::Sha2_256.to_u64() {
+            self.varints = 1;
+        }
+        if self.varints == 0 {
+            if value > Limits::DEFAULT.max_digest_len as u64 {
+                return Err(Error::DigestTooLarge);
+            }
+            self.bytes.resize(self.bytes.len() + value as usize, 0);
+        }
+        Ok(())
+    }
+
+    /// The buffer to read the digest into, once [`CidReader::step`] returns [`Step::Digest`].
+    pub(crate) fn digest_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes[self.start..]
+    }
+
+    /// Parse the CID, once the digest was read.
+    pub(crate) fn finish(self) -> Result<Cid> {
+        Cid::try_from(self.bytes.as_slice())
+    }
+}
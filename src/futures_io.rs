@@ -0,0 +1,45 @@
+//! Reading and writing CIDs with the runtime-agnostic `futures` async I/O traits.
+//!
+//! These are the counterparts of `Cid::read_async` and `Cid::write_async` of the `tokio` feature,
+//! for libraries that use `futures::io`, like libp2p protocol handlers.
+
+use std::io;
+
+use futures_util::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::cid::Cid;
+use crate::framing::{CidReader, Step};
+
+impl Cid {
+    /// Read exactly one encoded CID from a `futures` async reader.
+    ///
+    /// Invalid CIDs are reported as `InvalidData` errors, which wrap the [`Error`](crate::Error).
+    pub async fn read_futures_io<R: AsyncRead + Unpin + ?Sized>(r: &mut R) -> io::Result<Cid> {
+        let mut reader = CidReader::new();
+        loop {
+            match reader.step() {
+                Step::Byte => {
+                    let mut byte = [0];
+                    r.read_exact(&mut byte).await?;
+                    reader.push(byte[0])?;
+                }
+                Step::Digest => {
+                    r.read_exact(reader.digest_mut()).await?;
+                    return Ok(reader.finish()?);
+                }
+            }
+        }
+    }
+
+    /// Write the encoded bytes of the CID to a `futures` async writer.
+    ///
+    /// Returns the number of bytes written, which is always `encoded_len()`.
+    pub async fn write_futures_io<W: AsyncWrite + Unpin + ?Sized>(
+        &self,
+        w: &mut W,
+    ) -> io::Result<usize> {
+        let bytes = self.to_bytes();
+        w.write_all(&bytes).await?;
+        Ok(bytes.len())
+    }
+}
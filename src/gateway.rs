@@ -0,0 +1,154 @@
+//! IPFS HTTP gateway URLs.
+//!
+//! Path gateways serve content at `https://<host>/ipfs/<cid>/<path>`, subdomain gateways at
+//! `https://<cid>.ipfs.<host>/<path>`, so that every CID gets its own web origin. `/ipns/` names
+//! work the same way.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use multibase::Base;
+
+use crate::cid::Cid;
+use crate::error::{Error, Result};
+use crate::path::{self, IpfsPath, PathRoot};
+
+impl IpfsPath {
+    /// Parse the content path of a path or subdomain gateway URL.
+    ///
+    /// The query and fragment are ignored, and the path segments are kept percent-encoded.
+    /// DNSLink names inlined in a subdomain label, like `en-wikipedia--on--ipfs-org`, are turned
+    /// back into domain names. Returns `Error::InvalidUrl` for other URLs.
+    ///
+    /// ```
+    /// use cid::{IpfsPath, PathRoot};
+    ///
+    /// let url = "https://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi.ipfs.dweb.link/a?x=1";
+    /// let path = IpfsPath::from_gateway_url(url).unwrap();
+    /// assert!(matches!(path.root, PathRoot::Ipfs(_)));
+    /// assert_eq!(path.segments, ["a"]);
+    /// ```
+    pub fn from_gateway_url(url: &str) -> Result<Self> {
+        let rest = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .ok_or(Error::InvalidUrl)?;
+        let rest = rest.split(['?', '#']).next().unwrap_or_default();
+        let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+
+        let mut labels = host(authority).splitn(3, '.');
+        if let (Some(label), Some(namespace), Some(_)) =
+            (labels.next(), labels.next(), labels.next())
+        {
+            // Hosts like `gateway.ipfs.io` or `x.ipns.example.com`, whose first label isn't a
+            // CID or an inlined name, are path gateways.
+            let root = if namespace.eq_ignore_ascii_case("ipfs") {
+                Cid::try_from(label).ok().map(PathRoot::Ipfs)
+            } else if namespace.eq_ignore_ascii_case("ipns") {
+                ipns_name(label).map(PathRoot::Ipns)
+            } else {
+                None
+            };
+            if let Some(root) = root {
+                return Ok(IpfsPath {
+                    root,
+                    segments: path::segments(path).map(ToString::to_string).collect(),
+                });
+            }
+        }
+
+        path::parse_url_path(path)
+    }
+}
+
+/// The maximum length of a DNS label.
+const MAX_LABEL_LEN: usize = 63;
+
+/// How a gateway serves content.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum GatewayStyle {
+    /// `https://<host>/ipfs/<cid>`.
+    Path,
+    /// `https://<cid>.ipfs.<host>/`, which gives every CID its own web origin.
+    Subdomain,
+}
+
+impl Cid {
+    /// Return the URL of the CID on the gateway at `host`, e.g. `dweb.link`.
+    ///
+    /// Path gateway URLs keep the base of the CID. Subdomain gateway URLs need a case-insensitive
+    /// DNS label, see [`Cid::to_subdomain_label`].
+    ///
+    /// ```
+    /// use cid::{Cid, GatewayStyle};
+    ///
+    /// let cid: Cid = "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n".parse().unwrap();
+    /// assert_eq!(
+    ///     cid.to_gateway_url("dweb.link", GatewayStyle::Subdomain),
+    ///     "https://bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku.ipfs.dweb.link/"
+    /// );
+    /// ```
+    pub fn to_gateway_url(&self, host: &str, style: GatewayStyle) -> String {
+        match style {
+            GatewayStyle::Path => format!("https://{}/ipfs/{}", host, self),
+            GatewayStyle::Subdomain => {
+                format!("https://{}.ipfs.{}/", self.to_subdomain_label(), host)
+            }
+        }
+    }
+
+    /// Return the CID as a DNS label, for subdomain gateways.
+    ///
+    /// The CID is converted to CIDv1 in base32, or in base36 if that is longer than the 63
+    /// characters a DNS label is limited to, e.g. for libp2p-key CIDs of Ed25519 keys.
+    pub fn to_subdomain_label(&self) -> String {
+        let bytes = self.clone().into_v1().to_bytes();
+        let label = multibase::encode(Base::Base32Lower, &bytes);
+        if label.len() <= MAX_LABEL_LEN {
+            return label;
+        }
+        multibase::encode(Base::Base36Lower, &bytes)
+    }
+}
+
+/// Return the host of a URL authority, without the user info and port.
+fn host(authority: &str) -> &str {
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    match host.rfind(':') {
+        Some(colon) if host[colon + 1..].bytes().all(|byte| byte.is_ascii_digit()) => {
+            &host[..colon]
+        }
+        _ => host,
+    }
+}
+
+/// Return the IPNS name of a subdomain label, or `None` if it is neither a CID nor a domain name.
+///
+/// Domain names don't fit in a single label, so their `-` are escaped as `--` and their `.`
+/// replaced with `-`. CIDs, e.g. of libp2p keys, are used as is.
+fn ipns_name(label: &str) -> Option<String> {
+    if Cid::try_from(label).is_ok() {
+        return Some(label.to_string());
+    }
+    let name = label
+        .split("--")
+        .map(|part| part.replace('-', "."))
+        .collect::<Vec<_>>()
+        .join("-");
+    let is_domain = name.contains('.')
+        && name.split('.').all(|part| {
+            !part.is_empty()
+                && !part.starts_with('-')
+                && !part.ends_with('-')
+                && part
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+        });
+    if is_domain {
+        Some(name)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,31 @@
+//! async-graphql integration.
+//!
+//! A CID is a custom scalar, represented by its string representation. Invalid CIDs are rejected
+//! while parsing the input.
+
+use core::convert::TryFrom;
+
+use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+use crate::cid::Cid;
+
+/// A content identifier (CID).
+#[Scalar(name = "Cid", specified_by_url = "https://github.com/multiformats/cid")]
+impl ScalarType for Cid {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(string) => {
+                Cid::try_from(string.as_str()).map_err(InputValueError::custom)
+            }
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::String(string) if Cid::try_from(string.as_str()).is_ok())
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
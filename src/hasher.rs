@@ -0,0 +1,117 @@
+//! Incremental hashing for the multihash codes, which `multihash` only hashes in one go.
+
+use alloc::vec::Vec;
+
+use blake2b_simd::{Params as Blake2b, State as Blake2bState};
+use blake2s_simd::{Params as Blake2s, State as Blake2sState};
+use digest::Digest;
+use multihash::{Code, Multihash};
+
+use crate::error::{Error, Result};
+
+/// Hasher that is fed the data in chunks.
+pub(crate) enum Hasher {
+    Identity(Vec<u8>),
+    Sha1(sha1::Sha1),
+    Sha2_256(sha2::Sha256),
+    Sha2_512(sha2::Sha512),
+    Sha3_224(sha3::Sha3_224),
+    Sha3_256(sha3::Sha3_256),
+    Sha3_384(sha3::Sha3_384),
+    Sha3_512(sha3::Sha3_512),
+    Keccak224(sha3::Keccak224),
+    Keccak256(sha3::Keccak256),
+    Keccak384(sha3::Keccak384),
+    Keccak512(sha3::Keccak512),
+    Blake2b(Code, Blake2bState),
+    Blake2s(Code, Blake2sState),
+}
+
+impl Hasher {
+    /// Create a hasher for the multihash code.
+    ///
+    /// Returns `Error::UnsupportedMultihash` for custom codes.
+    pub(crate) fn new(code: Code) -> Result<Self> {
+        Ok(match code {
+            Code::Identity => Hasher::Identity(Vec::new()),
+            Code::Sha1 => Hasher::Sha1(Default::default()),
+            Code::Sha2_256 => Hasher::Sha2_256(Default::default()),
+            Code::Sha2_512 => Hasher::Sha2_512(Default::default()),
+            Code::Sha3_224 => Hasher::Sha3_224(Default::default()),
+            Code::Sha3_256 => Hasher::Sha3_256(Default::default()),
+            Code::Sha3_384 => Hasher::Sha3_384(Default::default()),
+            Code::Sha3_512 => Hasher::Sha3_512(Default::default()),
+            Code::Keccak224 => Hasher::Keccak224(Default::default()),
+            Code::Keccak256 => Hasher::Keccak256(Default::default()),
+            Code::Keccak384 => Hasher::Keccak384(Default::default()),
+            Code::Keccak512 => Hasher::Keccak512(Default::default()),
+            Code::Blake2b256 => Hasher::Blake2b(code, Blake2b::new().hash_length(32).to_state()),
+            Code::Blake2b512 => Hasher::Blake2b(code, Blake2b::new().hash_length(64).to_state()),
+            Code::Blake2s128 => Hasher::Blake2s(code, Blake2s::new().hash_length(16).to_state()),
+            Code::Blake2s256 => Hasher::Blake2s(code, Blake2s::new().hash_length(32).to_state()),
+            Code::Custom(_) => return Err(Error::UnsupportedMultihash),
+        })
+    }
+
+    /// Hash a chunk of the data.
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Identity(buf) => buf.extend_from_slice(data),
+            Hasher::Sha1(hasher) => hasher.input(data),
+            Hasher::Sha2_256(hasher) => hasher.input(data),
+            Hasher::Sha2_512(hasher) => hasher.input(data),
+            Hasher::Sha3_224(hasher) => hasher.input(data),
+            Hasher::Sha3_256(hasher) => hasher.input(data),
+            Hasher::Sha3_384(hasher) => hasher.input(data),
+            Hasher::Sha3_512(hasher) => hasher.input(data),
+            Hasher::Keccak224(hasher) => hasher.input(data),
+            Hasher::Keccak256(hasher) => hasher.input(data),
+            Hasher::Keccak384(hasher) => hasher.input(data),
+            Hasher::Keccak512(hasher) => hasher.input(data),
+            Hasher::Blake2b(_, state) => {
+                state.update(data);
+            }
+            Hasher::Blake2s(_, state) => {
+                state.update(data);
+            }
+        }
+    }
+
+    /// Hash all the data read from `r`, in chunks of up to `buf_size` bytes.
+    #[cfg(feature = "std")]
+    pub(crate) fn update_reader<R: std::io::Read>(
+        &mut self,
+        mut r: R,
+        buf_size: usize,
+    ) -> std::io::Result<()> {
+        let mut buf = vec![0; buf_size.max(1)];
+        loop {
+            match r.read(&mut buf) {
+                Ok(0) => return Ok(()),
+                Ok(len) => self.update(&buf[..len]),
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Return the multihash of all the data.
+    pub(crate) fn finalize(self) -> Multihash {
+        match self {
+            Hasher::Identity(buf) => multihash::wrap(Code::Identity, &buf),
+            Hasher::Sha1(hasher) => multihash::wrap(Code::Sha1, &hasher.result()),
+            Hasher::Sha2_256(hasher) => multihash::wrap(Code::Sha2_256, &hasher.result()),
+            Hasher::Sha2_512(hasher) => multihash::wrap(Code::Sha2_512, &hasher.result()),
+            Hasher::Sha3_224(hasher) => multihash::wrap(Code::Sha3_224, &hasher.result()),
+            Hasher::Sha3_256(hasher) => multihash::wrap(Code::Sha3_256, &hasher.result()),
+            Hasher::Sha3_384(hasher) => multihash::wrap(Code::Sha3_384, &hasher.result()),
+            Hasher::Sha3_512(hasher) => multihash::wrap(Code::Sha3_512, &hasher.result()),
+            Hasher::Keccak224(hasher) => multihash::wrap(Code::Keccak224, &hasher.result()),
+            Hasher::Keccak256(hasher) => multihash::wrap(Code::Keccak256, &hasher.result()),
+            Hasher::Keccak384(hasher) => multihash::wrap(Code::Keccak384, &hasher.result()),
+            Hasher::Keccak512(hasher) => multihash::wrap(Code::Keccak512, &hasher.result()),
+            Hasher::Blake2b(code, state) => multihash::wrap(code, state.finalize().as_bytes()),
+            Hasher::Blake2s(code, state) => multihash::wrap(code, state.finalize().as_bytes()),
+        }
+    }
+}
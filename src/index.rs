@@ -0,0 +1,324 @@
+//! A sorted, prefix-compressed index of CIDs, for CAR index sidecars and offline deduplication.
+//!
+//! The CIDs are sorted by their encoded bytes and front-coded: each CID is stored as the length of
+//! the prefix it shares with the previous one, followed by the rest of its bytes. Every 16th CID
+//! is stored in full, so lookups binary search those, then decode at most 16 CIDs.
+//!
+//! The serialized index is the magic bytes `CIDX`, a format version byte of 1, the restart
+//! interval and the number of CIDs as unsigned varints, then the CIDs, each as the unsigned varint
+//! lengths of the shared prefix and of the rest, followed by the rest.
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::FromIterator;
+
+use unsigned_varint::{decode as varint_decode, encode as varint_encode};
+
+use crate::cid::Cid;
+use crate::cid_ref::CidRef;
+use crate::error::{Error, Result};
+use crate::key::CidKey;
+use crate::limits::Limits;
+
+/// The magic bytes a serialized index starts with.
+const MAGIC: &[u8; 4] = b"CIDX";
+
+/// The version of the serialization format.
+const FORMAT_VERSION: u8 = 1;
+
+/// The number of CIDs from one stored in full to the next.
+const RESTART_INTERVAL: usize = 16;
+
+/// A sorted set of CIDs, compressed by sharing the prefixes of consecutive CIDs.
+///
+/// The index is built at once from the CIDs, which are sorted by their encoded bytes and
+/// deduplicated. Lookups accept any [`CidKey`] and return the position of the CID in that order,
+/// which can be used to index a table of block offsets stored next to the index.
+///
+/// ```
+/// use cid::{Cid, CidIndex, Codec};
+/// use multihash::Sha2_256;
+///
+/// let cids: Vec<Cid> = (0u32..100)
+///     .map(|i| Cid::new_v1(Codec::Raw, Sha2_256::digest(&i.to_le_bytes())))
+///     .collect();
+/// let index: CidIndex = cids.iter().cloned().collect();
+/// assert_eq!(index.len(), 100);
+/// assert!(index.contains(&cids[42]));
+///
+/// let bytes = index.to_bytes();
+/// assert_eq!(CidIndex::from_bytes(&bytes).unwrap(), index);
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct CidIndex {
+    /// The front-coded CIDs.
+    data: Vec<u8>,
+    /// The offset in `data` of each CID that is stored in full.
+    restarts: Vec<usize>,
+    len: usize,
+}
+
+impl CidIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the number of CIDs in the index.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether the CID is in the index.
+    pub fn contains<K: CidKey + ?Sized>(&self, cid: &K) -> bool {
+        self.position(cid).is_some()
+    }
+
+    /// Return the position of the CID in the index, in the order of the encoded bytes.
+    pub fn position<K: CidKey + ?Sized>(&self, cid: &K) -> Option<usize> {
+        let key = cid.cid_key();
+        // Find the last block whose first CID isn't after the key.
+        let mut buf = Vec::new();
+        let block = self.restarts.partition_point(|&offset| {
+            buf.clear();
+            decode_entry(&self.data[offset..], &mut buf).expect("entry of a valid index");
+            cmp_encoded(&buf, key) != Ordering::Greater
+        });
+        let block = block.checked_sub(1)?;
+
+        let mut data = &self.data[self.restarts[block]..];
+        buf.clear();
+        let start = block * RESTART_INTERVAL;
+        for pos in start..self.len.min(start + RESTART_INTERVAL) {
+            data = decode_entry(data, &mut buf).expect("entry of a valid index");
+            match cmp_encoded(&buf, key) {
+                Ordering::Less => {}
+                Ordering::Equal => return Some(pos),
+                Ordering::Greater => return None,
+            }
+        }
+        None
+    }
+
+    /// Return the CID at a position in the index.
+    pub fn get(&self, pos: usize) -> Option<Cid> {
+        if pos >= self.len {
+            return None;
+        }
+        let block = pos / RESTART_INTERVAL;
+        let mut iter = CidIndexIter {
+            data: &self.data[self.restarts[block]..],
+            buf: Vec::new(),
+            remaining: self.len - block * RESTART_INTERVAL,
+        };
+        iter.nth(pos % RESTART_INTERVAL)
+    }
+
+    /// Return an iterator over the CIDs of the index, in the order of their encoded bytes.
+    pub fn iter(&self) -> CidIndexIter<'_> {
+        CidIndexIter {
+            data: &self.data,
+            buf: Vec::new(),
+            remaining: self.len,
+        }
+    }
+
+    /// Serialize the index.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.header();
+        bytes.extend_from_slice(&self.data);
+        bytes
+    }
+
+    /// Deserialize an index.
+    ///
+    /// Returns `Error::InvalidIndex` if the bytes aren't a serialized index, or if its CIDs aren't
+    /// valid, canonically encoded and in strictly increasing order.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let rest = bytes.strip_prefix(MAGIC).ok_or(Error::InvalidIndex)?;
+        let rest = rest
+            .strip_prefix(&[FORMAT_VERSION])
+            .ok_or(Error::InvalidIndex)?;
+        let (interval, rest) = varint_decode::usize(rest).map_err(|_| Error::InvalidIndex)?;
+        let (len, data) = varint_decode::usize(rest).map_err(|_| Error::InvalidIndex)?;
+        if interval != RESTART_INTERVAL {
+            return Err(Error::InvalidIndex);
+        }
+
+        let mut restarts = Vec::new();
+        let mut buf = Vec::new();
+        let mut prev = Vec::new();
+        let mut rest = data;
+        let mut count = 0;
+        while !rest.is_empty() {
+            if count % RESTART_INTERVAL == 0 {
+                restarts.push(data.len() - rest.len());
+                // CIDs stored in full share nothing with the previous one.
+                buf.clear();
+            }
+            rest = decode_entry(rest, &mut buf).ok_or(Error::InvalidIndex)?;
+            check_cid(&buf, &prev)?;
+            prev.clone_from(&buf);
+            count += 1;
+        }
+        if count != len {
+            return Err(Error::InvalidIndex);
+        }
+        Ok(CidIndex {
+            data: data.to_vec(),
+            restarts,
+            len,
+        })
+    }
+
+    /// Write the serialized index to a writer.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: std::io::Write>(&self, mut w: W) -> std::io::Result<()> {
+        w.write_all(&self.header())?;
+        w.write_all(&self.data)
+    }
+
+    /// Read a serialized index from a reader, until its end.
+    ///
+    /// An invalid index is reported as an `InvalidData` error, which wraps the [`Error`].
+    #[cfg(feature = "std")]
+    pub fn read_from<R: std::io::Read>(mut r: R) -> std::io::Result<Self> {
+        let mut bytes = Vec::new();
+        r.read_to_end(&mut bytes)?;
+        Ok(Self::from_bytes(&bytes)?)
+    }
+
+    /// Return the serialized header of the index.
+    fn header(&self) -> Vec<u8> {
+        let mut header = MAGIC.to_vec();
+        header.push(FORMAT_VERSION);
+        let mut buf = varint_encode::usize_buffer();
+        header.extend_from_slice(varint_encode::usize(RESTART_INTERVAL, &mut buf));
+        let mut buf = varint_encode::usize_buffer();
+        header.extend_from_slice(varint_encode::usize(self.len, &mut buf));
+        header
+    }
+
+    /// Build the index from the encoded bytes of CIDs, sorted and deduplicated.
+    fn from_sorted(cids: &[Vec<u8>]) -> Self {
+        let mut index = CidIndex::new();
+        let mut prev: &[u8] = &[];
+        for (pos, cid) in cids.iter().enumerate() {
+            let shared = if pos % RESTART_INTERVAL == 0 {
+                index.restarts.push(index.data.len());
+                0
+            } else {
+                prev.iter().zip(cid).take_while(|(a, b)| a == b).count()
+            };
+            let mut buf = varint_encode::usize_buffer();
+            index
+                .data
+                .extend_from_slice(varint_encode::usize(shared, &mut buf));
+            let mut buf = varint_encode::usize_buffer();
+            index
+                .data
+                .extend_from_slice(varint_encode::usize(cid.len() - shared, &mut buf));
+            index.data.extend_from_slice(&cid[shared..]);
+            prev = cid;
+        }
+        index.len = cids.len();
+        index
+    }
+}
+
+/// Decode the CID at the start of `data` into `buf`, which holds the previous CID.
+///
+/// Returns the rest of `data`, or `None` if the entry is truncated or shares more than the
+/// previous CID.
+fn decode_entry<'a>(data: &'a [u8], buf: &mut Vec<u8>) -> Option<&'a [u8]> {
+    let (shared, rest) = varint_decode::usize(data).ok()?;
+    let (len, rest) = varint_decode::usize(rest).ok()?;
+    if shared > buf.len() || len > rest.len() {
+        return None;
+    }
+    buf.truncate(shared);
+    buf.extend_from_slice(&rest[..len]);
+    Some(&rest[len..])
+}
+
+/// Check that a decoded CID is valid, canonically encoded and after the previous one.
+fn check_cid(bytes: &[u8], prev: &[u8]) -> Result<()> {
+    let cid =
+        CidRef::from_bytes_with_limits(bytes, &Limits::NONE).map_err(|_| Error::InvalidIndex)?;
+    if cmp_encoded(bytes, cid.cid_key()) != Ordering::Equal || bytes <= prev {
+        return Err(Error::InvalidIndex);
+    }
+    Ok(())
+}
+
+/// Compare encoded bytes with the encoding of the CID with the version, codec and multihash.
+fn cmp_encoded(bytes: &[u8], (version, codec, hash): (u64, u64, &[u8])) -> Ordering {
+    if version == 0 {
+        return bytes.cmp(hash);
+    }
+    let mut version_buf = varint_encode::u64_buffer();
+    let version = varint_encode::u64(version, &mut version_buf);
+    let mut codec_buf = varint_encode::u64_buffer();
+    let codec = varint_encode::u64(codec, &mut codec_buf);
+    bytes
+        .iter()
+        .cmp(version.iter().chain(codec.iter()).chain(hash.iter()))
+}
+
+impl FromIterator<Cid> for CidIndex {
+    fn from_iter<I: IntoIterator<Item = Cid>>(iter: I) -> Self {
+        let mut cids: Vec<Vec<u8>> = iter.into_iter().map(|cid| cid.to_bytes()).collect();
+        cids.sort_unstable();
+        cids.dedup();
+        Self::from_sorted(&cids)
+    }
+}
+
+impl<'a> FromIterator<CidRef<'a>> for CidIndex {
+    fn from_iter<I: IntoIterator<Item = CidRef<'a>>>(iter: I) -> Self {
+        iter.into_iter().map(|cid| cid.to_owned()).collect()
+    }
+}
+
+impl<'a> IntoIterator for &'a CidIndex {
+    type Item = Cid;
+    type IntoIter = CidIndexIter<'a>;
+
+    fn into_iter(self) -> CidIndexIter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the CIDs of a [`CidIndex`], created with [`CidIndex::iter`].
+#[derive(Clone, Debug)]
+pub struct CidIndexIter<'a> {
+    data: &'a [u8],
+    /// The encoded bytes of the previous CID.
+    buf: Vec<u8>,
+    remaining: usize,
+}
+
+impl Iterator for CidIndexIter<'_> {
+    type Item = Cid;
+
+    fn next(&mut self) -> Option<Cid> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.data = decode_entry(self.data, &mut self.buf).expect("entry of a valid index");
+        // The CIDs were checked when the index was built or deserialized.
+        Some(Cid::from_bytes_with_limits(&self.buf, &Limits::NONE).expect("valid CID"))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for CidIndexIter<'_> {}
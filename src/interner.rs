@@ -0,0 +1,146 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::cid::Cid;
+
+/// A pool that deduplicates equal CIDs.
+///
+/// Interning a CID returns an [`InternedCid`] handle, which shares its CID with every other
+/// handle for an equal CID from the same interner. The interner can be shared between threads.
+#[derive(Debug, Default)]
+pub struct CidInterner {
+    cids: Mutex<HashSet<Arc<Cid>>>,
+}
+
+impl CidInterner {
+    /// Create an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the handle for a CID, adding it to the pool if it isn't there yet.
+    pub fn intern(&self, cid: &Cid) -> InternedCid {
+        let mut cids = self.lock();
+        match cids.get(cid) {
+            Some(interned) => InternedCid(interned.clone()),
+            None => {
+                let interned = Arc::new(cid.clone());
+                cids.insert(interned.clone());
+                InternedCid(interned)
+            }
+        }
+    }
+
+    /// Return the handle for a CID, if it is in the pool.
+    pub fn get(&self, cid: &Cid) -> Option<InternedCid> {
+        self.lock().get(cid).cloned().map(InternedCid)
+    }
+
+    /// Return the number of CIDs in the pool.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Remove the CIDs that are no longer referenced by any handle.
+    pub fn purge(&self) {
+        self.lock().retain(|cid| Arc::strong_count(cid) > 1);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashSet<Arc<Cid>>> {
+        // The set is never left in an inconsistent state, so a poisoned lock can be reused.
+        self.cids.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// A handle to a CID in a [`CidInterner`].
+///
+/// Cloning a handle only increments a reference count. Handles from the same interner compare
+/// by pointer first.
+#[derive(Clone)]
+pub struct InternedCid(Arc<Cid>);
+
+impl InternedCid {
+    /// Return the shared CID.
+    pub fn as_arc(&self) -> &Arc<Cid> {
+        &self.0
+    }
+}
+
+impl Deref for InternedCid {
+    type Target = Cid;
+
+    fn deref(&self) -> &Cid {
+        &self.0
+    }
+}
+
+impl AsRef<Cid> for InternedCid {
+    fn as_ref(&self) -> &Cid {
+        &self.0
+    }
+}
+
+impl From<InternedCid> for Arc<Cid> {
+    fn from(cid: InternedCid) -> Self {
+        cid.0
+    }
+}
+
+impl fmt::Debug for InternedCid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("InternedCid").field(&*self.0).finish()
+    }
+}
+
+impl fmt::Display for InternedCid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&*self.0, f)
+    }
+}
+
+impl PartialEq for InternedCid {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0 == other.0
+    }
+}
+
+impl Eq for InternedCid {}
+
+impl PartialEq<Cid> for InternedCid {
+    fn eq(&self, other: &Cid) -> bool {
+        *self.0 == *other
+    }
+}
+
+impl PartialEq<InternedCid> for Cid {
+    fn eq(&self, other: &InternedCid) -> bool {
+        *self == *other.0
+    }
+}
+
+impl PartialOrd for InternedCid {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for InternedCid {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl Hash for InternedCid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
@@ -0,0 +1,88 @@
+//! Lookups in maps and sets of CIDs by a borrowed form, without creating an owned [`Cid`].
+
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::hash::{Hash, Hasher};
+
+use crate::cid::Cid;
+use crate::cid_ref::CidRef;
+
+/// The key CIDs are compared, ordered and hashed by: the version, codec and multihash.
+///
+/// [`Cid`] borrows as `dyn CidKey`, so maps and sets of `Cid` can be queried with any `CidKey`,
+/// like a [`CidRef`] parsed from the wire:
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use cid::{Cid, CidKey, CidRef};
+///
+/// let cid: Cid = "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+///     .parse()
+///     .unwrap();
+/// let bytes = cid.to_bytes();
+/// let mut blocks = HashMap::new();
+/// blocks.insert(cid, "block");
+///
+/// let key = CidRef::from_bytes(&bytes).unwrap();
+/// assert_eq!(blocks.get(&key as &dyn CidKey), Some(&"block"));
+/// ```
+///
+/// With the `hashbrown` feature, a `CidRef` is also `Equivalent` to a `Cid`, so it can be used
+/// directly to query `hashbrown` maps, and `indexmap` maps, which use the same trait.
+pub trait CidKey {
+    /// The version, codec and bytes of the multihash of the CID.
+    fn cid_key(&self) -> (u64, u64, &[u8]);
+}
+
+impl CidKey for Cid {
+    fn cid_key(&self) -> (u64, u64, &[u8]) {
+        self.order_key()
+    }
+}
+
+impl CidKey for CidRef<'_> {
+    fn cid_key(&self) -> (u64, u64, &[u8]) {
+        self.order_key()
+    }
+}
+
+impl<'a> Borrow<dyn CidKey + 'a> for Cid {
+    fn borrow(&self) -> &(dyn CidKey + 'a) {
+        self
+    }
+}
+
+impl PartialEq for dyn CidKey + '_ {
+    fn eq(&self, other: &Self) -> bool {
+        self.cid_key() == other.cid_key()
+    }
+}
+
+impl Eq for dyn CidKey + '_ {}
+
+impl PartialOrd for dyn CidKey + '_ {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for dyn CidKey + '_ {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cid_key().cmp(&other.cid_key())
+    }
+}
+
+impl Hash for dyn CidKey + '_ {
+    /// Hashes the same as [`Cid`].
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cid_key().hash(state);
+    }
+}
+
+#[cfg(feature = "hashbrown")]
+impl hashbrown::Equivalent<Cid> for CidRef<'_> {
+    fn equivalent(&self, key: &Cid) -> bool {
+        self == key
+    }
+}
@@ -1,17 +1,163 @@
 //! # cid
 //!
 //! Implementation of [cid](https://github.com/ipld/cid) in Rust.
+//!
+//! Without the default `std` feature the crate itself only uses `core` and `alloc`, but it doesn't
+//! build for targets without `std` yet: `multihash` 0.10 and `unsigned-varint` 0.3 always need it.
 
 #![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
+#[cfg(feature = "actix")]
+pub mod actix;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "rayon")]
+pub mod batch;
+#[cfg(feature = "bloom")]
+mod bloom;
+#[cfg(feature = "borsh")]
+mod borsh;
+#[cfg(feature = "bson")]
+pub mod bson;
+mod builder;
+#[cfg(feature = "std")]
+mod cached;
 mod cid;
+mod cid_generic;
+mod cid_n;
+mod cid_ref;
+#[cfg(feature = "clap")]
+pub mod clap;
 mod codec;
+#[cfg(feature = "hasher")]
+mod corpus;
+#[cfg(feature = "dag-cbor")]
+pub mod dag_cbor;
+#[cfg(feature = "serde")]
+pub mod dag_json;
+#[cfg(feature = "defmt")]
+mod defmt;
+#[cfg(feature = "diesel")]
+mod diesel;
+pub mod dshelp;
+mod encoding;
 mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+mod framing;
+#[cfg(feature = "futures-io")]
+mod futures_io;
+mod gateway;
+#[cfg(feature = "async-graphql")]
+mod graphql;
+#[cfg(feature = "hasher")]
+mod hasher;
+mod index;
+#[cfg(feature = "std")]
+mod interner;
+mod key;
+#[cfg(feature = "libp2p")]
+mod libp2p;
+mod limits;
+mod lines;
+#[cfg(feature = "minicbor")]
+mod minicbor;
+mod path;
+#[cfg(feature = "postgres-types")]
+mod postgres;
 mod prefix;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "prost")]
+mod proto;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+#[cfg(feature = "random")]
+mod random;
+#[cfg(feature = "redis")]
+pub mod redis;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "rocket")]
+mod rocket;
+#[cfg(feature = "rusqlite")]
+mod rusqlite;
+#[cfg(feature = "scale")]
+mod scale;
+mod scan;
+#[cfg(any(feature = "schemars", feature = "utoipa"))]
+mod schema;
+#[cfg(feature = "schemars")]
+mod schemars;
+#[cfg(feature = "std")]
+mod seq;
+#[cfg(feature = "serde")]
+mod serde;
+mod set;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+#[cfg(feature = "tokio")]
+mod tokio;
+#[cfg(feature = "tracing")]
+mod tracing;
+#[cfg(feature = "uniffi")]
+pub mod uniffi;
+#[cfg(feature = "url")]
+mod url;
+#[cfg(feature = "utoipa")]
+mod utoipa;
 mod version;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
+#[cfg(feature = "bloom")]
+pub use self::bloom::CidBloom;
+pub use self::builder::CidBuilder;
+#[cfg(feature = "std")]
+pub use self::cached::CachedCid;
 pub use self::cid::Cid;
+pub use self::cid_generic::{CidGeneric, CodeTable};
+pub use self::cid_n::CidN;
+pub use self::cid_ref::CidRef;
 pub use self::codec::Codec;
-pub use self::error::{Error, Result};
+#[cfg(feature = "hasher")]
+pub use self::corpus::{corpus, Corpus};
+pub use self::error::{Cause, Component, Error, Mismatch, Result};
+pub use self::gateway::GatewayStyle;
+pub use self::index::{CidIndex, CidIndexIter};
+#[cfg(feature = "std")]
+pub use self::interner::{CidInterner, InternedCid};
+pub use self::key::CidKey;
+pub use self::limits::Limits;
+#[cfg(feature = "std")]
+pub use self::lines::{parse_lines, ParseLines};
+pub use self::lines::{CidLinesExt, ParseCids};
+pub use self::path::{IpfsPath, PathRoot};
 pub use self::prefix::Prefix;
+#[cfg(feature = "prost")]
+pub use self::proto::CidProto;
+#[cfg(feature = "random")]
+pub use self::random::RandomCidConfig;
+#[cfg(feature = "rkyv")]
+pub use self::rkyv::{ArchivedCid, CheckCidError};
+pub use self::scan::{scan, Scan};
+#[cfg(feature = "std")]
+pub use self::seq::{CidSeqReader, CidSeqWriter};
+pub use self::set::{CidSet, CidSetIter};
 pub use self::version::Version;
+
+pub use multibase::Base;
+
+#[cfg(feature = "macros")]
+pub use cid_macros::cid;
+
+#[cfg(feature = "uniffi")]
+::uniffi::setup_scaffolding!();
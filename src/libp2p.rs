@@ -0,0 +1,41 @@
+//! CID conversions to and from libp2p PeerIds.
+//!
+//! A PeerId is represented as a CIDv1 with the libp2p-key codec and the PeerId's multihash.
+
+use core::convert::TryFrom;
+
+use libp2p_identity::PeerId;
+use multihash::Multihash;
+
+use crate::cid::Cid;
+use crate::error::{Error, Result};
+
+impl From<PeerId> for Cid {
+    fn from(peer_id: PeerId) -> Self {
+        let hash = Multihash::from_bytes(peer_id.to_bytes()).expect("PeerId must be a multihash");
+        Cid::from_peer_key_multihash(hash).expect("PeerId must be an identity or Sha-256 multihash")
+    }
+}
+
+impl From<&PeerId> for Cid {
+    fn from(peer_id: &PeerId) -> Self {
+        Cid::from(*peer_id)
+    }
+}
+
+impl TryFrom<Cid> for PeerId {
+    type Error = Error;
+
+    fn try_from(cid: Cid) -> Result<Self> {
+        let hash = cid.try_into_peer_multihash()?;
+        PeerId::from_bytes(hash.as_bytes()).map_err(|_| Error::InvalidPeerId)
+    }
+}
+
+impl TryFrom<&Cid> for PeerId {
+    type Error = Error;
+
+    fn try_from(cid: &Cid) -> Result<Self> {
+        PeerId::try_from(cid.clone())
+    }
+}
@@ -0,0 +1,38 @@
+/// Limits enforced while parsing a CID.
+///
+/// Parsing untrusted input with [`Limits::DEFAULT`], which is what `TryFrom` and `FromStr` do,
+/// bounds the work done and the memory used for a single CID.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Limits {
+    /// The maximum length of the multihash digest, in bytes.
+    pub max_digest_len: usize,
+    /// The maximum length of the encoded CID, in bytes.
+    pub max_len: usize,
+}
+
+impl Limits {
+    /// The default limits: digests of up to 128 bytes and CIDs of up to 512 bytes.
+    pub const DEFAULT: Limits = Limits {
+        max_digest_len: 128,
+        max_len: 512,
+    };
+
+    /// No limits, for input that is trusted.
+    pub const NONE: Limits = Limits {
+        max_digest_len: usize::MAX,
+        max_len: usize::MAX,
+    };
+
+    /// The maximum length of a string that can encode a CID within these limits.
+    ///
+    /// Base2 is the least dense multibase, with 8 characters per byte plus the base code.
+    pub(crate) fn max_str_len(&self) -> usize {
+        self.max_len.saturating_mul(8).saturating_add(1)
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
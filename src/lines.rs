@@ -0,0 +1,94 @@
+//! Parsing lists of CIDs, one per line, as in pin dumps and allowlists.
+//!
+//! Whitespace around the CIDs is ignored, as are empty lines and comments, which start with `#`
+//! and run to the end of the line.
+
+use core::convert::TryFrom;
+
+use crate::cid::Cid;
+use crate::error::Result;
+
+/// Return the CID string of a line, or `None` if there is none.
+fn cid_str(line: &str) -> Option<&str> {
+    let line = match line.find('#') {
+        Some(comment) => &line[..comment],
+        None => line,
+    };
+    Some(line.trim()).filter(|s| !s.is_empty())
+}
+
+/// Iterator adapter that parses the CIDs of lines, see [`CidLinesExt::parse_cids`].
+#[derive(Clone, Debug)]
+pub struct ParseCids<I> {
+    lines: I,
+}
+
+impl<I> Iterator for ParseCids<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = Result<Cid>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for line in &mut self.lines {
+            if let Some(s) = cid_str(line.as_ref()) {
+                return Some(Cid::try_from(s));
+            }
+        }
+        None
+    }
+}
+
+/// Extension trait to parse the CIDs of an iterator over lines.
+pub trait CidLinesExt: Iterator + Sized
+where
+    Self::Item: AsRef<str>,
+{
+    /// Parse every line that isn't empty or a comment as a CID.
+    fn parse_cids(self) -> ParseCids<Self> {
+        ParseCids { lines: self }
+    }
+}
+
+impl<I> CidLinesExt for I
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+}
+
+/// Iterator over the CIDs of the lines read from a reader, see [`parse_lines`].
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ParseLines<R> {
+    lines: std::io::Lines<R>,
+}
+
+/// Parse the CIDs of the lines read from a reader.
+///
+/// Invalid CIDs are reported as `InvalidData` errors, which wrap the [`Error`](crate::Error).
+#[cfg(feature = "std")]
+pub fn parse_lines<R: std::io::BufRead>(reader: R) -> ParseLines<R> {
+    ParseLines {
+        lines: reader.lines(),
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::BufRead> Iterator for ParseLines<R> {
+    type Item = std::io::Result<Cid>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for line in &mut self.lines {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => return Some(Err(err)),
+            };
+            if let Some(s) = cid_str(&line) {
+                return Some(Cid::try_from(s).map_err(Into::into));
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,36 @@
+//! CID minicbor (de)serialization.
+//!
+//! A CID is encoded as in DAG-CBOR: a byte string with tag 42, containing the binary CID
+//! prefixed with a `0x00` byte.
+
+use minicbor::data::Tag;
+use minicbor::{decode, encode, Decoder, Encoder};
+
+use crate::cid::Cid;
+use crate::dag_cbor::{self, CBOR_TAG_CID};
+
+impl<C> minicbor::Encode<C> for Cid {
+    fn encode<W: encode::Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut C,
+    ) -> Result<(), encode::Error<W::Error>> {
+        e.tag(Tag::new(CBOR_TAG_CID))?
+            .bytes(&dag_cbor::to_tag_payload(self))?
+            .ok()
+    }
+}
+
+impl<'b, C> minicbor::Decode<'b, C> for Cid {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut C) -> Result<Self, decode::Error> {
+        let position = d.position();
+        let tag = d.tag()?;
+        if tag.as_u64() != CBOR_TAG_CID {
+            return Err(decode::Error::tag_mismatch(tag).at(position));
+        }
+
+        let position = d.position();
+        dag_cbor::from_tag_payload(d.bytes()?)
+            .map_err(|err| decode::Error::message(err).at(position))
+    }
+}
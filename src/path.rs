@@ -0,0 +1,203 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::cid::Cid;
+use crate::error::{Error, Result};
+
+/// The root of an [`IpfsPath`].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum PathRoot {
+    /// The CID of an `/ipfs/` path.
+    Ipfs(Cid),
+    /// The name of an `/ipns/` path, e.g. a libp2p-key CID or a DNSLink domain.
+    Ipns(String),
+}
+
+/// An `/ipfs/<cid>/...` or `/ipns/<name>/...` content path.
+///
+/// Empty segments, from repeated or trailing slashes, are dropped, so the path is rendered back
+/// without them.
+///
+/// ```
+/// use cid::{IpfsPath, PathRoot};
+///
+/// let path: IpfsPath = "/ipns/example.com/a/b".parse().unwrap();
+/// assert_eq!(path.root, PathRoot::Ipns("example.com".to_string()));
+/// assert_eq!(path.segments, ["a", "b"]);
+/// assert_eq!(path.to_string(), "/ipns/example.com/a/b");
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct IpfsPath {
+    /// The CID or IPNS name the path starts at.
+    pub root: PathRoot,
+    /// The segments of the path after the root.
+    pub segments: Vec<String>,
+}
+
+impl IpfsPath {
+    /// Create a path to the root of a CID.
+    pub fn new(cid: Cid) -> Self {
+        IpfsPath {
+            root: PathRoot::Ipfs(cid),
+            segments: Vec::new(),
+        }
+    }
+
+    /// Return the CID of an `/ipfs/` path, or `None` for an `/ipns/` path.
+    pub fn cid(&self) -> Option<&Cid> {
+        match &self.root {
+            PathRoot::Ipfs(cid) => Some(cid),
+            PathRoot::Ipns(_) => None,
+        }
+    }
+
+    /// Return the segments after the root joined with `/`, without a leading slash.
+    pub fn remainder(&self) -> String {
+        self.segments.join("/")
+    }
+
+    /// Parse an `ipfs://<cid>/...`, `ipns://<name>/...` or `dweb:/ipfs/<cid>/...` URI.
+    ///
+    /// The query and fragment are ignored. Returns `Error::InvalidUrl` for other URIs.
+    pub fn from_uri(uri: &str) -> Result<Self> {
+        let uri = uri.split(['?', '#']).next().unwrap_or_default();
+        if let Some(path) = uri.strip_prefix("dweb:") {
+            return parse_url_path(path);
+        }
+        let (scheme, rest) = uri.split_once("://").ok_or(Error::InvalidUrl)?;
+        let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+        let root = match scheme {
+            "ipfs" => PathRoot::Ipfs(Cid::try_from(authority)?),
+            "ipns" if !authority.is_empty() => PathRoot::Ipns(authority.to_string()),
+            _ => return Err(Error::InvalidUrl),
+        };
+        Ok(IpfsPath {
+            root,
+            segments: segments(path).map(ToString::to_string).collect(),
+        })
+    }
+
+    /// Return the path as an `ipfs://` or `ipns://` URI.
+    ///
+    /// The authority of a URI is case-insensitive, so CIDs are written as CIDv1 in base32, see
+    /// [`Cid::to_subdomain_label`].
+    pub fn to_uri(&self) -> String {
+        let mut uri = match &self.root {
+            PathRoot::Ipfs(cid) => format!("ipfs://{}", cid.to_subdomain_label()),
+            PathRoot::Ipns(name) => format!("ipns://{}", name),
+        };
+        for segment in &self.segments {
+            uri.push('/');
+            uri.push_str(segment);
+        }
+        uri
+    }
+
+    /// Return the path as a `dweb:` URI, which keeps the base of the CID.
+    pub fn to_dweb_uri(&self) -> String {
+        format!("dweb:{}", self)
+    }
+
+    /// Parse the value of a DNSLink TXT record, like `dnslink=/ipfs/<cid>`.
+    ///
+    /// Whitespace around the value is ignored. Returns `Error::InvalidDnsLink` if the value doesn't
+    /// start with `dnslink=` followed by an `/ipfs/` or `/ipns/` path.
+    pub fn from_dnslink(txt: &str) -> Result<Self> {
+        let path = txt
+            .trim()
+            .strip_prefix("dnslink=")
+            .ok_or(Error::InvalidDnsLink)?;
+        path.parse().map_err(|err| match err {
+            Error::InvalidPath => Error::InvalidDnsLink,
+            err => err,
+        })
+    }
+
+    /// Return the value of a DNSLink TXT record pointing to the path.
+    pub fn to_dnslink(&self) -> String {
+        format!("dnslink={}", self)
+    }
+}
+
+/// Parse the content path of a URL, returning `Error::InvalidUrl` if it isn't one.
+pub(crate) fn parse_url_path(path: &str) -> Result<IpfsPath> {
+    path.parse().map_err(|err| match err {
+        Error::InvalidPath => Error::InvalidUrl,
+        err => err,
+    })
+}
+
+/// Split a path into its segments, dropping the empty ones.
+pub(crate) fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+impl FromStr for IpfsPath {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<Self> {
+        if !path.starts_with('/') {
+            return Err(Error::InvalidPath);
+        }
+        let mut segments = segments(path);
+        let root = match (segments.next(), segments.next()) {
+            (Some("ipfs"), Some(cid)) => PathRoot::Ipfs(Cid::try_from(cid)?),
+            (Some("ipns"), Some(name)) => PathRoot::Ipns(name.to_string()),
+            _ => return Err(Error::InvalidPath),
+        };
+        Ok(IpfsPath {
+            root,
+            segments: segments.map(ToString::to_string).collect(),
+        })
+    }
+}
+
+impl TryFrom<&str> for IpfsPath {
+    type Error = Error;
+
+    fn try_from(path: &str) -> Result<Self> {
+        path.parse()
+    }
+}
+
+impl fmt::Display for IpfsPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.root {
+            PathRoot::Ipfs(cid) => write!(f, "/ipfs/{}", cid)?,
+            PathRoot::Ipns(name) => write!(f, "/ipns/{}", name)?,
+        }
+        self.segments
+            .iter()
+            .try_for_each(|segment| write!(f, "/{}", segment))
+    }
+}
+
+impl From<Cid> for IpfsPath {
+    fn from(cid: Cid) -> Self {
+        IpfsPath::new(cid)
+    }
+}
+
+impl TryFrom<&IpfsPath> for Cid {
+    type Error = Error;
+
+    /// Return the root CID of an `/ipfs/` path, or `Error::InvalidPath` for an `/ipns/` path.
+    fn try_from(path: &IpfsPath) -> Result<Self> {
+        path.cid().cloned().ok_or(Error::InvalidPath)
+    }
+}
+
+impl TryFrom<IpfsPath> for Cid {
+    type Error = Error;
+
+    fn try_from(path: IpfsPath) -> Result<Self> {
+        match path.root {
+            PathRoot::Ipfs(cid) => Ok(cid),
+            PathRoot::Ipns(_) => Err(Error::InvalidPath),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! postgres-types integration.
+//!
+//! A CID can be stored in a `BYTEA` column (its encoded bytes) or in a text column (its string
+//! representation).
+
+use core::convert::TryFrom;
+use std::error::Error;
+
+use bytes::BytesMut;
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use crate::cid::Cid;
+
+impl ToSql for Cid {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        if <&[u8] as ToSql>::accepts(ty) {
+            self.to_bytes().to_sql(ty, out)
+        } else {
+            self.to_string().to_sql(ty, out)
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&[u8] as ToSql>::accepts(ty) || <&str as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Cid {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let cid = if <&[u8] as FromSql>::accepts(ty) {
+            Cid::try_from(<&[u8] as FromSql>::from_sql(ty, raw)?)?
+        } else {
+            Cid::try_from(<&str as FromSql>::from_sql(ty, raw)?)?
+        };
+        Ok(cid)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&[u8] as FromSql>::accepts(ty) || <&str as FromSql>::accepts(ty)
+    }
+}
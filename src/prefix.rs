@@ -1,7 +1,9 @@
+use alloc::vec::Vec;
+
 use unsigned_varint::{decode as varint_decode, encode as varint_encode};
 
 use crate::codec::Codec;
-use crate::error::{Error, Result};
+use crate::error::Result;
 use crate::version::Version;
 
 /// Prefix represents all metadata of a CID, without the actual content.
@@ -12,6 +14,9 @@ pub struct Prefix {
     /// The codec of CID.
     pub codec: Codec,
     /// The multihash type of CID.
+    ///
+    /// Codes that are not known to `multihash` (e.g. private-use codes) are kept as
+    /// `Code::Custom`.
     pub mh_type: multihash::Code,
     /// The multihash length of CID.
     pub mh_len: usize,
@@ -24,13 +29,10 @@ impl Prefix {
         let version = Version::from(raw_version)?;
 
         let (raw_codec, remain) = varint_decode::u64(remain)?;
-        let codec = Codec::from(raw_codec)?;
+        let codec = Codec::from_code(raw_codec);
 
         let (raw_mh_type, remain) = varint_decode::u64(remain)?;
-        let mh_type = match multihash::Code::from_u64(raw_mh_type) {
-            multihash::Code::Custom(_) => return Err(Error::UnknownCodec),
-            code => code,
-        };
+        let mh_type = multihash::Code::from_u64(raw_mh_type);
 
         let (mh_len, _remain) = varint_decode::usize(remain)?;
 
@@ -0,0 +1,139 @@
+//! `proptest` strategies for CIDs.
+//!
+//! The CIDs are valid and within the default [`Limits`](crate::Limits), so they survive a round
+//! trip through their binary and string representations.
+//!
+//! ```
+//! use std::convert::TryFrom;
+//!
+//! use cid::Cid;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn roundtrip(cid in cid::proptest::any_cid()) {
+//!         prop_assert_eq!(Cid::try_from(cid.to_bytes().as_slice()).unwrap(), cid);
+//!     }
+//! }
+//! # roundtrip();
+//! ```
+
+use alloc::string::String;
+
+use multibase::Base;
+use multihash::{Code, Multihash};
+use proptest::collection::vec as vec_of;
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use crate::cid::Cid;
+use crate::codec::Codec;
+use crate::version::Version;
+
+/// The multihash codes there is a hash function for.
+const CODES: &[Code] = &[
+    Code::Identity,
+    Code::Sha1,
+    Code::Sha2_256,
+    Code::Sha2_512,
+    Code::Sha3_224,
+    Code::Sha3_256,
+    Code::Sha3_384,
+    Code::Sha3_512,
+    Code::Keccak224,
+    Code::Keccak256,
+    Code::Keccak384,
+    Code::Keccak512,
+    Code::Blake2b256,
+    Code::Blake2b512,
+    Code::Blake2s128,
+    Code::Blake2s256,
+];
+
+/// Every base, except the identity base, which isn't valid UTF-8 for most CIDs.
+const BASES: &[Base] = &[
+    Base::Base2,
+    Base::Base8,
+    Base::Base10,
+    Base::Base16Lower,
+    Base::Base16Upper,
+    Base::Base32Lower,
+    Base::Base32Upper,
+    Base::Base32PadLower,
+    Base::Base32PadUpper,
+    Base::Base32HexLower,
+    Base::Base32HexUpper,
+    Base::Base32HexPadLower,
+    Base::Base32HexPadUpper,
+    Base::Base32Z,
+    Base::Base36Lower,
+    Base::Base36Upper,
+    Base::Base58Flickr,
+    Base::Base58Btc,
+    Base::Base64,
+    Base::Base64Pad,
+    Base::Base64Url,
+    Base::Base64UrlPad,
+];
+
+/// Any codec, mostly with the small codes of the known codecs.
+pub fn any_codec() -> impl Strategy<Value = Codec> {
+    prop_oneof![
+        4 => (0u64..0x200).prop_map(Codec::from_code),
+        1 => any::<u64>().prop_map(Codec::from_code),
+    ]
+}
+
+/// Any multihash code with a hash function.
+pub fn any_code() -> impl Strategy<Value = Code> {
+    select(CODES)
+}
+
+/// The multihash of random data, hashed with a function of the strategy.
+///
+/// The data is at most 128 bytes long, so identity multihashes are within the default limits.
+pub fn multihash(code: impl Strategy<Value = Code>) -> impl Strategy<Value = Multihash> {
+    (code, vec_of(any::<u8>(), 0..=128)).prop_map(|(code, data)| {
+        code.hasher()
+            .expect("strategy codes have a hash function")
+            .digest(&data)
+    })
+}
+
+/// Any multihash with a hash function.
+pub fn any_multihash() -> impl Strategy<Value = Multihash> {
+    multihash(any_code())
+}
+
+/// A CIDv0, of the SHA2-256 multihash of random data.
+pub fn cid_v0() -> impl Strategy<Value = Cid> {
+    multihash(Just(Code::Sha2_256)).prop_map(|hash| Cid::new_v0(hash).expect("SHA2-256"))
+}
+
+/// A CIDv1 with a codec and multihash of the strategies.
+pub fn cid_v1(
+    codec: impl Strategy<Value = Codec>,
+    hash: impl Strategy<Value = Multihash>,
+) -> impl Strategy<Value = Cid> {
+    (codec, hash).prop_map(|(codec, hash)| Cid::new_v1(codec, hash))
+}
+
+/// Any CID, mostly CIDv1.
+pub fn any_cid() -> impl Strategy<Value = Cid> {
+    prop_oneof![
+        1 => cid_v0(),
+        4 => cid_v1(any_codec(), any_multihash()),
+    ]
+}
+
+/// Any multibase that can be used for CID strings.
+pub fn any_base() -> impl Strategy<Value = Base> {
+    select(BASES)
+}
+
+/// The string representation of any CID, CIDv1 in any base.
+pub fn cid_string_any_base() -> impl Strategy<Value = String> {
+    (any_cid(), any_base()).prop_map(|(cid, base)| match cid.version {
+        Version::V0 => cid.to_string(),
+        Version::V1 => cid.to_string_of_base(base).expect("CIDv1 in any base"),
+    })
+}
@@ -0,0 +1,54 @@
+//! Protobuf interop.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::cid::Cid;
+use crate::error::{Error, Result};
+
+/// A protobuf message containing a single CID, as encoded bytes.
+///
+/// ```protobuf
+/// message Cid {
+///   bytes cid = 1;
+/// }
+/// ```
+///
+/// Converting it into a [`Cid`] validates the bytes. Plain `bytes` fields of other messages can
+/// be converted with the `From`/`TryFrom` implementations between `Cid` and `Vec<u8>`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CidProto {
+    /// The encoded CID.
+    #[prost(bytes = "vec", tag = "1")]
+    pub cid: Vec<u8>,
+}
+
+impl From<Cid> for CidProto {
+    fn from(cid: Cid) -> Self {
+        Self::from(&cid)
+    }
+}
+
+impl From<&Cid> for CidProto {
+    fn from(cid: &Cid) -> Self {
+        CidProto {
+            cid: cid.to_bytes(),
+        }
+    }
+}
+
+impl TryFrom<CidProto> for Cid {
+    type Error = Error;
+
+    fn try_from(proto: CidProto) -> Result<Self> {
+        Cid::try_from(proto.cid)
+    }
+}
+
+impl TryFrom<&CidProto> for Cid {
+    type Error = Error;
+
+    fn try_from(proto: &CidProto) -> Result<Self> {
+        Cid::try_from(proto.cid.as_slice())
+    }
+}
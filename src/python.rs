@@ -0,0 +1,111 @@
+//! Python bindings, via PyO3.
+//!
+//! [`PyCid`] is exported to Python as the `Cid` class. Use [`register`] to add it to an extension
+//! module.
+
+use alloc::borrow::Cow;
+use core::convert::TryFrom;
+
+use multibase::Base;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::cid::Cid;
+use crate::error::Error;
+
+impl From<Error> for PyErr {
+    fn from(err: Error) -> Self {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+/// A CID, exported to Python.
+#[pyclass(name = "Cid", module = "cid", frozen, eq, hash, ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PyCid(Cid);
+
+#[pymethods]
+impl PyCid {
+    /// Parse a CID from its string representation.
+    #[new]
+    fn new(cid: &str) -> PyResult<Self> {
+        Ok(PyCid(Cid::try_from(cid)?))
+    }
+
+    /// Parse a CID from its string representation.
+    #[staticmethod]
+    fn parse(cid: &str) -> PyResult<Self> {
+        Self::new(cid)
+    }
+
+    /// Decode a CID from its encoded bytes.
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        Ok(PyCid(Cid::try_from(bytes)?))
+    }
+
+    /// Encode the CID as string, with the multibase of the given code (e.g. `"z"` for base58btc),
+    /// or its default base.
+    #[pyo3(signature = (base = None))]
+    fn encode(&self, base: Option<char>) -> PyResult<String> {
+        match base {
+            Some(code) => {
+                let base = Base::from_code(code).map_err(Error::from)?;
+                Ok(self.0.to_string_of_base(base)?)
+            }
+            None => Ok(self.0.to_string()),
+        }
+    }
+
+    /// The encoded bytes of the CID.
+    fn to_bytes(&self) -> Cow<'static, [u8]> {
+        Cow::Owned(self.0.to_bytes())
+    }
+
+    /// The version of the CID.
+    #[getter]
+    fn version(&self) -> u64 {
+        self.0.version.into()
+    }
+
+    /// The multicodec code of the CID.
+    #[getter]
+    fn codec(&self) -> u64 {
+        self.0.codec.into()
+    }
+
+    /// The multihash of the CID.
+    #[getter]
+    fn multihash(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.0.hash.as_bytes())
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Cid('{}')", self.0)
+    }
+
+    fn __bytes__(&self) -> Cow<'static, [u8]> {
+        self.to_bytes()
+    }
+}
+
+impl From<Cid> for PyCid {
+    fn from(cid: Cid) -> Self {
+        PyCid(cid)
+    }
+}
+
+impl From<PyCid> for Cid {
+    fn from(cid: PyCid) -> Self {
+        cid.0
+    }
+}
+
+/// Add the `Cid` class to a Python module.
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyCid>()
+}
@@ -0,0 +1,108 @@
+//! `quickcheck::Arbitrary` implementations.
+//!
+//! The CIDs are within the default [`Limits`], so they survive a round trip through their binary
+//! and string representations. Their digests are arbitrary bytes, not hashes of any data.
+//!
+//! CIDs shrink to CIDv0 where that is valid, then their digests shrink towards zeros and, for
+//! CIDv1, towards shorter digests and smaller codec codes.
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use multihash::Code;
+use quickcheck::{Arbitrary, Gen};
+
+use crate::cid::Cid;
+use crate::codec::Codec;
+use crate::limits::Limits;
+use crate::version::Version;
+
+/// The codes of the common multihashes, which are picked most of the time.
+const CODES: &[u64] = &[0x00, 0x11, 0x12, 0x13, 0x14, 0x16, 0x1b, 0xb220, 0xb260];
+
+impl Arbitrary for Version {
+    fn arbitrary(g: &mut Gen) -> Self {
+        *g.choose(&[Version::V0, Version::V1]).unwrap()
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        match self {
+            Version::V0 => quickcheck::empty_shrinker(),
+            Version::V1 => quickcheck::single_shrinker(Version::V0),
+        }
+    }
+}
+
+impl Arbitrary for Codec {
+    /// Mostly small codes, where the known codecs are.
+    fn arbitrary(g: &mut Gen) -> Self {
+        let code = u64::arbitrary(g);
+        if bool::arbitrary(g) || bool::arbitrary(g) {
+            Codec::from_code(code % 0x200)
+        } else {
+            Codec::from_code(code)
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(u64::from(*self).shrink().map(Codec::from_code))
+    }
+}
+
+impl Arbitrary for Cid {
+    fn arbitrary(g: &mut Gen) -> Self {
+        if bool::arbitrary(g) {
+            let digest: Vec<u8> = (0..32).map(|_| u8::arbitrary(g)).collect();
+            return Cid::new_v0(multihash::wrap(Code::Sha2_256, &digest)).expect("SHA2-256");
+        }
+        let code = if bool::arbitrary(g) || bool::arbitrary(g) {
+            *g.choose(CODES).unwrap()
+        } else {
+            u64::arbitrary(g)
+        };
+        let len = usize::arbitrary(g) % (Limits::DEFAULT.max_digest_len + 1);
+        let digest: Vec<u8> = (0..len).map(|_| u8::arbitrary(g)).collect();
+        Cid::new_v1(
+            Codec::arbitrary(g),
+            multihash::wrap(Code::from_u64(code), &digest),
+        )
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let mut shrunk = Vec::new();
+        // CIDv0 digests can't be truncated.
+        if self.version == Version::V1 && self.digest().len() == 32 {
+            if let Ok(v0) = self.clone().try_into_v0() {
+                shrunk.push(v0);
+            }
+        }
+
+        let code = self.hash.algorithm();
+        let digest = self.digest();
+        let with_digest = |digest: &[u8]| Cid {
+            hash: multihash::wrap(code, digest),
+            ..self.clone()
+        };
+        if digest.iter().any(|byte| *byte != 0) {
+            shrunk.push(with_digest(&vec![0; digest.len()]));
+            // Zero one byte at a time, so a failure that needs some of them is still found.
+            for (i, _) in digest.iter().enumerate().filter(|(_, byte)| **byte != 0) {
+                let mut zeroed = digest.to_vec();
+                zeroed[i] = 0;
+                shrunk.push(with_digest(&zeroed));
+            }
+        }
+
+        if self.version == Version::V1 {
+            if !digest.is_empty() {
+                shrunk.push(with_digest(&digest[..digest.len() / 2]));
+            }
+            shrunk.extend(self.codec.shrink().map(|codec| Cid {
+                codec,
+                ..self.clone()
+            }));
+        }
+        Box::new(shrunk.into_iter())
+    }
+}
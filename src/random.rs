@@ -0,0 +1,141 @@
+//! Random CIDs, for tests and load generation.
+//!
+//! The digests are hashes of random data, so they have the length of the hash function. Custom
+//! multihash codes, which have no hash function, get random 32-byte digests.
+
+use alloc::vec::Vec;
+
+use multihash::{Code, Multihash};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::cid::Cid;
+use crate::codec::Codec;
+use crate::hasher::Hasher;
+use crate::version::Version;
+
+/// The length of the random data that is hashed, which is also the length of the digests of
+/// identity and custom multihashes.
+const DATA_LEN: usize = 32;
+
+/// Return the multihash of random data.
+fn random_hash<R: Rng + ?Sized>(code: Code, rng: &mut R) -> Multihash {
+    let mut data = [0; DATA_LEN];
+    rng.fill(&mut data[..]);
+    match Hasher::new(code) {
+        Ok(mut hasher) => {
+            hasher.update(&data);
+            hasher.finalize()
+        }
+        Err(_) => multihash::wrap(code, &data),
+    }
+}
+
+/// Pick an item with a probability proportional to its weight, or `default` if all weights are
+/// zero.
+fn pick<T: Copy, R: Rng + ?Sized>(items: &[(T, u32)], default: T, rng: &mut R) -> T {
+    let total: u64 = items.iter().map(|(_, weight)| u64::from(*weight)).sum();
+    if total == 0 {
+        return default;
+    }
+    let mut n = rng.gen_range(0..total);
+    for (item, weight) in items {
+        let weight = u64::from(*weight);
+        if n < weight {
+            return *item;
+        }
+        n -= weight;
+    }
+    unreachable!("n is less than the total weight")
+}
+
+impl Cid {
+    /// Create a random CIDv0.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Cid {
+        Cid::new_v0_unchecked(random_hash(Code::Sha2_256, rng))
+    }
+
+    /// Create a random CIDv1 with the codec and hash function.
+    pub fn random_v1<R: Rng + ?Sized>(codec: Codec, code: Code, rng: &mut R) -> Cid {
+        Cid::new_v1(codec, random_hash(code, rng))
+    }
+}
+
+/// Configuration of a weighted mix of random CIDs.
+///
+/// Each version, codec and hash function is picked with a probability proportional to its weight.
+/// The codecs and hash functions apply to CIDv1 only. By default it makes CIDv1 with the Raw codec
+/// and a SHA2-256 multihash, like `rng.gen::<Cid>()`. It is also a [`Distribution`], to be used
+/// with `rng.sample(&config)` or `rng.sample_iter(&config)`:
+///
+/// ```
+/// use cid::{Codec, RandomCidConfig};
+/// use multihash::Code;
+/// use rand::rngs::mock::StepRng;
+///
+/// let config = RandomCidConfig::new()
+///     .codec(Codec::DagCBOR, 3)
+///     .codec(Codec::Raw, 1)
+///     .hash_with(Code::Blake2b256, 1)
+///     .hash_with(Code::Sha2_256, 1);
+/// let mut rng = StepRng::new(1, 0x9e37_79b9_7f4a_7c15);
+/// let cid = config.generate(&mut rng);
+/// assert!(cid.codec == Codec::DagCBOR || cid.codec == Codec::Raw);
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct RandomCidConfig {
+    versions: Vec<(Version, u32)>,
+    codecs: Vec<(Codec, u32)>,
+    codes: Vec<(Code, u32)>,
+}
+
+impl RandomCidConfig {
+    /// Create a configuration with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a version to the mix. Without any, only CIDv1 are made.
+    pub fn version(mut self, version: Version, weight: u32) -> Self {
+        self.versions.push((version, weight));
+        self
+    }
+
+    /// Add a codec to the mix. Without any, the Raw codec is used.
+    pub fn codec(mut self, codec: Codec, weight: u32) -> Self {
+        self.codecs.push((codec, weight));
+        self
+    }
+
+    /// Add a hash function to the mix. Without any, SHA2-256 is used.
+    pub fn hash_with(mut self, code: Code, weight: u32) -> Self {
+        self.codes.push((code, weight));
+        self
+    }
+
+    /// Create a random CID of the mix.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> Cid {
+        match pick(&self.versions, Version::V1, rng) {
+            Version::V0 => Cid::random(rng),
+            Version::V1 => {
+                let codec = pick(&self.codecs, Codec::Raw, rng);
+                let code = pick(&self.codes, Code::Sha2_256, rng);
+                Cid::random_v1(codec, code, rng)
+            }
+        }
+    }
+}
+
+impl Distribution<Cid> for RandomCidConfig {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Cid {
+        self.generate(rng)
+    }
+}
+
+impl Distribution<Cid> for Standard {
+    /// A CIDv1 with the Raw codec and a SHA2-256 multihash, as made by the default
+    /// [`RandomCidConfig`].
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Cid {
+        Cid::random_v1(Codec::Raw, Code::Sha2_256, rng)
+    }
+}
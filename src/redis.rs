@@ -0,0 +1,57 @@
+//! Redis integration.
+//!
+//! By default a CID is written as its encoded bytes. Wrap it in [`AsString`] to write its string
+//! representation instead. Both representations are accepted when reading a value back.
+
+use core::convert::TryFrom;
+
+use redis::{ErrorKind, FromRedisValue, RedisError, RedisResult, RedisWrite, ToRedisArgs, Value};
+
+use crate::cid::Cid;
+use crate::error::Error;
+
+/// Writes a CID as Redis argument in its string representation.
+#[derive(Clone, Copy, Debug)]
+pub struct AsString<'a>(pub &'a Cid);
+
+impl ToRedisArgs for Cid {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(&self.to_bytes());
+    }
+}
+
+impl<'a> ToRedisArgs for AsString<'a> {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg_fmt(self.0);
+    }
+}
+
+impl FromRedisValue for Cid {
+    fn from_redis_value(value: &Value) -> RedisResult<Self> {
+        let cid = match value {
+            Value::BulkString(bytes) => from_bytes(bytes),
+            Value::SimpleString(string) => Cid::try_from(string.as_str()),
+            _ => {
+                return Err(RedisError::from((
+                    ErrorKind::TypeError,
+                    "Response type not convertible to CID",
+                )))
+            }
+        };
+        cid.map_err(|err| RedisError::from((ErrorKind::TypeError, "Invalid CID", err.to_string())))
+    }
+}
+
+/// Decode a CID from a Redis string, either its encoded bytes or its string representation.
+fn from_bytes(bytes: &[u8]) -> Result<Cid, Error> {
+    Cid::try_from(bytes).or_else(|err| match core::str::from_utf8(bytes) {
+        Ok(string) => Cid::try_from(string),
+        Err(_) => Err(err),
+    })
+}
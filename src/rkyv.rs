@@ -0,0 +1,137 @@
+//! CID rkyv archiving.
+//!
+//! A CID is archived as its encoded bytes. Validating an archive (with `rkyv::check_archived_root`
+//! and friends) also checks that those bytes are a valid CID, so an [`ArchivedCid`] can be
+//! inspected and compared in place, without deserializing it first.
+
+use core::convert::TryFrom;
+use core::fmt;
+
+use rkyv::bytecheck::CheckBytes;
+use rkyv::ser::{ScratchSpace, Serializer};
+use rkyv::vec::{ArchivedVec, VecResolver};
+use rkyv::{Archive, Deserialize, Fallible, Serialize};
+
+use crate::cid::Cid;
+use crate::cid_ref::CidRef;
+use crate::error::{Error, Result};
+
+/// An archived [`Cid`].
+#[repr(transparent)]
+pub struct ArchivedCid(ArchivedVec<u8>);
+
+impl ArchivedCid {
+    /// The encoded bytes of the CID.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    /// Return a borrowed CID, without copying the archived bytes.
+    ///
+    /// This never fails for a validated archive.
+    pub fn as_cid_ref(&self) -> Result<CidRef<'_>> {
+        CidRef::from_bytes(self.as_bytes())
+    }
+}
+
+impl fmt::Debug for ArchivedCid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ArchivedCid")
+            .field(&self.as_bytes())
+            .finish()
+    }
+}
+
+impl PartialEq for ArchivedCid {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for ArchivedCid {}
+
+impl core::hash::Hash for ArchivedCid {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl PartialEq<Cid> for ArchivedCid {
+    fn eq(&self, other: &Cid) -> bool {
+        self.as_cid_ref().is_ok_and(|cid| cid == *other)
+    }
+}
+
+impl PartialEq<ArchivedCid> for Cid {
+    fn eq(&self, other: &ArchivedCid) -> bool {
+        other == self
+    }
+}
+
+impl Archive for Cid {
+    type Archived = ArchivedCid;
+    type Resolver = VecResolver;
+
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        ArchivedVec::<u8>::resolve_from_len(self.encoded_len(), pos, resolver, out.cast());
+    }
+}
+
+impl<S: Serializer + ScratchSpace + ?Sized> Serialize<S> for Cid {
+    fn serialize(&self, serializer: &mut S) -> core::result::Result<Self::Resolver, S::Error> {
+        ArchivedVec::<u8>::serialize_from_slice(&self.to_bytes(), serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Cid, D> for ArchivedCid {
+    /// # Panics
+    ///
+    /// Panics if the archive wasn't validated and doesn't contain a valid CID.
+    fn deserialize(&self, _: &mut D) -> core::result::Result<Cid, D::Error> {
+        Ok(Cid::try_from(self.as_bytes()).expect("archived CID must be valid"))
+    }
+}
+
+/// Error returned when validating an [`ArchivedCid`].
+#[derive(Debug)]
+pub enum CheckCidError<E> {
+    /// The archived bytes are invalid.
+    Bytes(E),
+    /// The archived bytes are not a valid CID.
+    Cid(Error),
+}
+
+impl<E: fmt::Display> fmt::Display for CheckCidError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckCidError::Bytes(err) => write!(f, "Invalid archived CID bytes: {}", err),
+            CheckCidError::Cid(err) => write!(f, "Invalid archived CID: {}", err),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for CheckCidError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CheckCidError::Bytes(err) => Some(err),
+            CheckCidError::Cid(err) => Some(err),
+        }
+    }
+}
+
+impl<C: ?Sized> CheckBytes<C> for ArchivedCid
+where
+    ArchivedVec<u8>: CheckBytes<C>,
+{
+    type Error = CheckCidError<<ArchivedVec<u8> as CheckBytes<C>>::Error>;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> core::result::Result<&'a Self, Self::Error> {
+        let bytes =
+            ArchivedVec::<u8>::check_bytes(value.cast(), context).map_err(CheckCidError::Bytes)?;
+        CidRef::from_bytes(bytes.as_slice()).map_err(CheckCidError::Cid)?;
+        Ok(&*value)
+    }
+}
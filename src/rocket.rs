@@ -0,0 +1,27 @@
+//! Rocket integration.
+//!
+//! A [`Cid`] can be used as dynamic path parameter and as form field. An invalid CID in the path
+//! forwards the request with `422 Unprocessable Entity`, which is the status the catcher gets if no
+//! other route matches, and an invalid CID in a form is a validation error, with the same status.
+
+use core::convert::TryFrom;
+
+use rocket::form::{self, FromFormField, ValueField};
+use rocket::request::FromParam;
+
+use crate::cid::Cid;
+use crate::error::Error;
+
+impl<'a> FromParam<'a> for Cid {
+    type Error = Error;
+
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        Cid::try_from(param)
+    }
+}
+
+impl<'v> FromFormField<'v> for Cid {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        Cid::try_from(field.value).map_err(|err| form::Error::validation(err.to_string()).into())
+    }
+}
@@ -0,0 +1,27 @@
+//! rusqlite integration.
+//!
+//! A CID is stored as a blob of its encoded bytes. It can be read back from either a blob or a
+//! text column.
+
+use core::convert::TryFrom;
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use crate::cid::Cid;
+
+impl ToSql for Cid {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_bytes()))
+    }
+}
+
+impl FromSql for Cid {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let cid = match value {
+            ValueRef::Blob(bytes) => Cid::try_from(bytes),
+            ValueRef::Text(_) => Cid::try_from(value.as_str()?),
+            _ => return Err(FromSqlError::InvalidType),
+        };
+        cid.map_err(|err| FromSqlError::Other(Box::new(err)))
+    }
+}
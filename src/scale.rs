@@ -0,0 +1,43 @@
+//! CID SCALE codec and type information.
+//!
+//! A CID is encoded like a `Vec<u8>` of its encoded bytes, i.e. with a compact length prefix.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use parity_scale_codec::{Compact, Decode, Encode, EncodeLike, Error, Input, Output};
+use scale_info::build::Fields;
+use scale_info::{Path, Type, TypeInfo};
+
+use crate::cid::Cid;
+
+impl Encode for Cid {
+    fn size_hint(&self) -> usize {
+        let len = self.encoded_len();
+        Compact(len as u32).size_hint() + len
+    }
+
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        self.to_bytes().encode_to(dest)
+    }
+}
+
+impl EncodeLike for Cid {}
+
+impl Decode for Cid {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+        let bytes = Vec::<u8>::decode(input)?;
+        Cid::try_from(bytes).map_err(|_| "Invalid CID".into())
+    }
+}
+
+impl TypeInfo for Cid {
+    type Identity = Self;
+
+    fn type_info() -> Type {
+        Type::builder()
+            .path(Path::new("Cid", "cid"))
+            .docs(&["A CID, encoded as its binary representation."])
+            .composite(Fields::unnamed().field(|f| f.ty::<Vec<u8>>().type_name("Vec<u8>")))
+    }
+}
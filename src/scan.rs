@@ -0,0 +1,61 @@
+//! Finding CIDs in arbitrary text, such as logs, HTML or JSON.
+//!
+//! The text is split into runs of ASCII letters and digits. A run is a candidate if it is a 46
+//! character CIDv0 starting with `Qm`, or starts with the code of one of the multibases in
+//! [`PREFIXES`]. Candidates are then parsed with the default [`Limits`](crate::Limits), and only
+//! valid CIDs are returned.
+
+use core::convert::TryFrom;
+use core::ops::Range;
+
+use crate::cid::Cid;
+
+/// The codes of the multibases whose alphabet only has ASCII letters and digits: base32 and
+/// base36 in either case, base16 in either case and base58btc.
+const PREFIXES: &[char] = &['b', 'B', 'k', 'K', 'f', 'F', 'z'];
+
+/// Find the CIDs in `text`.
+///
+/// Returns the byte range and the CID of each match, in order. A CID is only found if it isn't
+/// directly preceded or followed by other letters or digits.
+pub fn scan(text: &str) -> Scan<'_> {
+    Scan { text, pos: 0 }
+}
+
+/// Iterator over the CIDs found in a text, see [`scan`].
+#[derive(Clone, Debug)]
+pub struct Scan<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Iterator for Scan<'_> {
+    type Item = (Range<usize>, Cid);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.text.as_bytes();
+        while let Some(offset) = bytes[self.pos..].iter().position(u8::is_ascii_alphanumeric) {
+            // Both ends are ASCII, so they are on char boundaries.
+            let start = self.pos + offset;
+            let end = bytes[start..]
+                .iter()
+                .position(|byte| !byte.is_ascii_alphanumeric())
+                .map_or(bytes.len(), |len| start + len);
+            self.pos = end;
+            if let Some(cid) = candidate(&self.text[start..end]) {
+                return Some((start..end, cid));
+            }
+        }
+        self.pos = bytes.len();
+        None
+    }
+}
+
+/// Parse a run of letters and digits, if it looks like a CID.
+fn candidate(token: &str) -> Option<Cid> {
+    let is_v0 = token.len() == 46 && token.starts_with("Qm");
+    if !is_v0 && !token.starts_with(PREFIXES) {
+        return None;
+    }
+    Cid::try_from(token).ok()
+}
@@ -0,0 +1,14 @@
+//! Shared description of the string representation of a CID, for schema generators.
+
+/// The description of a CID string.
+pub(crate) const DESCRIPTION: &str = "A content identifier (CID), as CIDv0 (base58btc, starting \
+                                      with `Qm`) or multibase encoded CIDv1 string";
+
+/// The format name of a CID string.
+pub(crate) const FORMAT: &str = "cid";
+
+/// A pattern matching CID strings.
+///
+/// This accepts CIDv0 strings and multibase strings with an alphanumeric prefix. It doesn't check
+/// that the string decodes to a valid CID.
+pub(crate) const PATTERN: &str = "^(Qm[1-9A-HJ-NP-Za-km-z]{44}|[0-9A-Za-z][0-9A-Za-z+/=_-]+)$";
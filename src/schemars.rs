@@ -0,0 +1,41 @@
+//! schemars integration.
+//!
+//! A CID is described by its string representation, as used by human-readable serde formats.
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Metadata, Schema, SchemaObject, StringValidation};
+use schemars::JsonSchema;
+
+use crate::cid::Cid;
+use crate::schema;
+
+impl JsonSchema for Cid {
+    fn schema_name() -> String {
+        "Cid".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("cid::Cid")
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                description: Some(schema::DESCRIPTION.into()),
+                ..Default::default()
+            })),
+            instance_type: Some(InstanceType::String.into()),
+            format: Some(schema::FORMAT.into()),
+            string: Some(Box::new(StringValidation {
+                pattern: Some(schema::PATTERN.into()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
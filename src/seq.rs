@@ -0,0 +1,110 @@
+//! Sequences of varint length prefixed CIDs, as used in CARv1 headers and pin lists.
+
+use std::io::{self, Read, Write};
+
+use unsigned_varint::{decode as varint_decode, encode as varint_encode};
+
+use crate::cid::Cid;
+use crate::error::Error;
+use crate::limits::Limits;
+
+/// Iterator over the CIDs of a sequence read from a reader.
+///
+/// Each CID is prefixed with its length as unsigned varint. The iterator ends at the end of the
+/// reader, invalid CIDs are reported as `InvalidData` errors, which wrap the [`Error`].
+#[derive(Debug)]
+pub struct CidSeqReader<R> {
+    reader: R,
+    limits: Limits,
+}
+
+impl<R: Read> CidSeqReader<R> {
+    /// Create a reader that checks the CIDs against [`Limits::DEFAULT`].
+    pub fn new(reader: R) -> Self {
+        Self::with_limits(reader, Limits::DEFAULT)
+    }
+
+    /// Create a reader that checks the CIDs against the given limits.
+    pub fn with_limits(reader: R, limits: Limits) -> Self {
+        Self { reader, limits }
+    }
+
+    /// Return the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Read the length prefix, or `None` at the end of the reader.
+    fn read_len(&mut self) -> io::Result<Option<u64>> {
+        let mut buf = varint_encode::u64_buffer();
+        // Only the end of the reader before the first byte is the end of the sequence.
+        loop {
+            match self.reader.read(&mut buf[..1]) {
+                Ok(0) => return Ok(None),
+                Ok(_) => break,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        let mut last = 0;
+        while !varint_decode::is_last(buf[last]) && last + 1 < buf.len() {
+            last += 1;
+            self.reader.read_exact(&mut buf[last..=last])?;
+        }
+        let (len, _) = varint_decode::u64(&buf[..=last]).map_err(Error::from)?;
+        Ok(Some(len))
+    }
+
+    fn read_cid(&mut self) -> io::Result<Option<Cid>> {
+        let len = match self.read_len()? {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+        if len > self.limits.max_len as u64 {
+            return Err(Error::InputTooLong.into());
+        }
+        let mut bytes = vec![0; len as usize];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(Some(Cid::from_bytes_with_limits(&bytes, &self.limits)?))
+    }
+}
+
+impl<R: Read> Iterator for CidSeqReader<R> {
+    type Item = io::Result<Cid>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_cid().transpose()
+    }
+}
+
+/// Writer of a sequence of CIDs, each prefixed with its length as unsigned varint.
+#[derive(Debug)]
+pub struct CidSeqWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> CidSeqWriter<W> {
+    /// Create a writer.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Write a CID, prefixed with its length.
+    pub fn write(&mut self, cid: &Cid) -> io::Result<()> {
+        let mut buf = varint_encode::u64_buffer();
+        self.writer
+            .write_all(varint_encode::u64(cid.encoded_len() as u64, &mut buf))?;
+        cid.write_bytes(&mut self.writer)?;
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
@@ -0,0 +1,74 @@
+//! CID Serde (de)serialization.
+//!
+//! Human-readable formats (JSON, YAML, ...) use the string representation of the CID, binary
+//! formats (bincode, postcard, ...) use its encoded bytes.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+use serde::{de, ser};
+
+use crate::cid::Cid;
+
+impl ser::Serialize for Cid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(&self.to_bytes())
+        }
+    }
+}
+
+/// Visitor to deserialize a CID from either its string representation or its encoded bytes.
+struct CidVisitor;
+
+impl<'de> de::Visitor<'de> for CidVisitor {
+    type Value = Cid;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("a valid CID in string or byte representation")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Cid::try_from(value).map_err(de::Error::custom)
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Cid::try_from(value).map_err(de::Error::custom)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut bytes: Vec<u8> = Vec::new();
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        self.visit_bytes(&bytes)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Cid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(CidVisitor)
+        } else {
+            deserializer.deserialize_bytes(CidVisitor)
+        }
+    }
+}
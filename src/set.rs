@@ -0,0 +1,313 @@
+//! A compact set of CIDs, for large collections like the blocks of a DAG.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter::FromIterator;
+
+use unsigned_varint::{decode as varint_decode, encode as varint_encode};
+
+use crate::cid::Cid;
+use crate::cid_ref::CidRef;
+use crate::key::CidKey;
+use crate::limits::Limits;
+
+/// The smallest number of slots of a non-empty index.
+const MIN_SLOTS: usize = 8;
+
+/// A set of CIDs, stored as their encoded bytes.
+///
+/// The CIDs are appended to a single buffer and found through an open-addressing index hashed on
+/// their digests, so a set takes up the encoded length of its CIDs plus about 16 bytes per CID,
+/// without an allocation per CID. CIDs can be inserted and looked up by any [`CidKey`], like a
+/// [`Cid`] or a [`CidRef`], and are iterated over as [`CidRef`]s, in insertion order:
+///
+/// ```
+/// use cid::{Cid, CidRef, CidSet};
+///
+/// let cid: Cid = "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+///     .parse()
+///     .unwrap();
+/// let mut set = CidSet::new();
+/// assert!(set.insert(&cid));
+/// assert!(!set.insert(&cid));
+///
+/// let bytes = cid.to_bytes();
+/// assert!(set.contains(&CidRef::from_bytes(&bytes).unwrap()));
+/// assert_eq!(set.iter().next().unwrap(), cid);
+/// ```
+#[derive(Clone, Default)]
+pub struct CidSet {
+    /// The encoded bytes of the CIDs, one after the other.
+    bytes: Vec<u8>,
+    /// The offset of each CID in `bytes`.
+    starts: Vec<usize>,
+    /// The slots of the index, holding one plus the position of a CID in `starts`, or zero.
+    slots: Vec<u32>,
+}
+
+impl CidSet {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty set with room for `capacity` CIDs before its index grows.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut set = Self::new();
+        set.starts.reserve(capacity);
+        set.rehash(slots_for(capacity));
+        set
+    }
+
+    /// Return the number of CIDs in the set.
+    pub fn len(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// Add a CID to the set. Returns whether it wasn't in the set yet.
+    pub fn insert<K: CidKey + ?Sized>(&mut self, cid: &K) -> bool {
+        let key = cid.cid_key();
+        if self.find(key).is_ok() {
+            return false;
+        }
+        self.push(key);
+        true
+    }
+
+    /// Whether the CID is in the set.
+    pub fn contains<K: CidKey + ?Sized>(&self, cid: &K) -> bool {
+        self.find(cid.cid_key()).is_ok()
+    }
+
+    /// Return an iterator over the CIDs of the set, in insertion order.
+    pub fn iter(&self) -> CidSetIter<'_> {
+        CidSetIter { set: self, pos: 0 }
+    }
+
+    /// Return the set of the CIDs that are in `self`, `other` or both.
+    pub fn union(&self, other: &CidSet) -> CidSet {
+        let mut set = self.clone();
+        for cid in other {
+            set.insert(&cid);
+        }
+        set
+    }
+
+    /// Return the set of the CIDs that are both in `self` and `other`.
+    pub fn intersection(&self, other: &CidSet) -> CidSet {
+        self.iter().filter(|cid| other.contains(cid)).collect()
+    }
+
+    /// Return the set of the CIDs that are in `self` but not in `other`.
+    pub fn difference(&self, other: &CidSet) -> CidSet {
+        self.iter().filter(|cid| !other.contains(cid)).collect()
+    }
+
+    /// Return the encoded bytes of the CID at `pos` in `starts`.
+    fn entry(&self, pos: usize) -> &[u8] {
+        let end = self.starts.get(pos + 1).copied();
+        &self.bytes[self.starts[pos]..end.unwrap_or(self.bytes.len())]
+    }
+
+    /// Return the CID at `pos` in `starts`.
+    fn get(&self, pos: usize) -> CidRef<'_> {
+        // The bytes were encoded from a valid CID, which may be over the default limits.
+        CidRef::from_bytes_with_limits(self.entry(pos), &Limits::NONE)
+            .expect("bytes of a valid CID")
+    }
+
+    /// Return the slot of the CID, or the empty slot where it would go.
+    fn find(&self, (version, codec, hash): (u64, u64, &[u8])) -> Result<usize, usize> {
+        if self.slots.is_empty() {
+            return Err(0);
+        }
+        let mask = self.slots.len() - 1;
+        let mut slot = hash_digest(digest(hash)) as usize & mask;
+        loop {
+            match self.slots[slot] {
+                0 => return Err(slot),
+                pos if encodes(self.entry(pos as usize - 1), version, codec, hash) => {
+                    return Ok(slot)
+                }
+                _ => slot = (slot + 1) & mask,
+            }
+        }
+    }
+
+    /// Append a CID that isn't in the set yet, growing the index if needed.
+    fn push(&mut self, (version, codec, hash): (u64, u64, &[u8])) {
+        // Keep the index at most three quarters full.
+        if (self.len() + 1) * 4 > self.slots.len() * 3 {
+            self.rehash(slots_for(self.len() + 1).max(self.slots.len() * 2));
+        }
+        let pos = u32::try_from(self.len() + 1).expect("fewer than 2^32 - 1 CIDs in a set");
+        self.starts.push(self.bytes.len());
+        if version != 0 {
+            let mut buf = varint_encode::u64_buffer();
+            self.bytes
+                .extend_from_slice(varint_encode::u64(version, &mut buf));
+            let mut buf = varint_encode::u64_buffer();
+            self.bytes
+                .extend_from_slice(varint_encode::u64(codec, &mut buf));
+        }
+        self.bytes.extend_from_slice(hash);
+        let slot = self
+            .find((version, codec, hash))
+            .expect_err("the CID wasn't in the set");
+        self.slots[slot] = pos;
+    }
+
+    /// Rebuild the index with `len` slots, a power of two.
+    fn rehash(&mut self, len: usize) {
+        let mut slots = vec![0; len];
+        let mask = len.wrapping_sub(1);
+        for pos in 0..self.len() {
+            let mut slot = hash_digest(self.get(pos).digest()) as usize & mask;
+            while slots[slot] != 0 {
+                slot = (slot + 1) & mask;
+            }
+            slots[slot] = pos as u32 + 1;
+        }
+        self.slots = slots;
+    }
+}
+
+/// Return the number of slots of an index for `len` CIDs.
+fn slots_for(len: usize) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    (len * 4 / 3 + 1).next_power_of_two().max(MIN_SLOTS)
+}
+
+/// Return the digest of a multihash.
+pub(crate) fn digest(hash: &[u8]) -> &[u8] {
+    varint_decode::u64(hash)
+        .and_then(|(_, rest)| varint_decode::u64(rest))
+        .map_or(hash, |(_, digest)| digest)
+}
+
+/// Hash a digest for the index.
+///
+/// Digests are mostly the output of a cryptographic hash function already, but identity and
+/// custom multihashes aren't, so all the bytes are mixed in.
+pub(crate) fn hash_digest(digest: &[u8]) -> u64 {
+    let mut hash = digest.len() as u64;
+    for chunk in digest.chunks(8) {
+        let mut word = [0; 8];
+        word[..chunk.len()].copy_from_slice(chunk);
+        hash = (hash.rotate_left(5) ^ u64::from_le_bytes(word)).wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
+    // The multiplications only carry upwards, so fold the high bits into the low ones, which are
+    // used for the slots.
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^ (hash >> 33)
+}
+
+/// Whether `bytes` are the encoding of the CID with the version, codec and multihash.
+fn encodes(bytes: &[u8], version: u64, codec: u64, hash: &[u8]) -> bool {
+    if version == 0 {
+        return bytes == hash;
+    }
+    let mut buf = varint_encode::u64_buffer();
+    let version = varint_encode::u64(version, &mut buf);
+    let mut buf = varint_encode::u64_buffer();
+    let codec = varint_encode::u64(codec, &mut buf);
+    bytes
+        .strip_prefix(version)
+        .and_then(|rest| rest.strip_prefix(codec))
+        == Some(hash)
+}
+
+impl fmt::Debug for CidSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set()
+            .entries(self.iter().map(|cid| cid.to_owned()))
+            .finish()
+    }
+}
+
+impl PartialEq for CidSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|cid| other.contains(&cid))
+    }
+}
+
+impl Eq for CidSet {}
+
+impl<'a> Extend<CidRef<'a>> for CidSet {
+    fn extend<I: IntoIterator<Item = CidRef<'a>>>(&mut self, iter: I) {
+        for cid in iter {
+            self.insert(&cid);
+        }
+    }
+}
+
+impl Extend<Cid> for CidSet {
+    fn extend<I: IntoIterator<Item = Cid>>(&mut self, iter: I) {
+        for cid in iter {
+            self.insert(&cid);
+        }
+    }
+}
+
+impl<'a> FromIterator<CidRef<'a>> for CidSet {
+    fn from_iter<I: IntoIterator<Item = CidRef<'a>>>(iter: I) -> Self {
+        let mut set = CidSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl FromIterator<Cid> for CidSet {
+    fn from_iter<I: IntoIterator<Item = Cid>>(iter: I) -> Self {
+        let mut set = CidSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a> IntoIterator for &'a CidSet {
+    type Item = CidRef<'a>;
+    type IntoIter = CidSetIter<'a>;
+
+    fn into_iter(self) -> CidSetIter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the CIDs of a [`CidSet`], created with [`CidSet::iter`].
+#[derive(Clone, Debug)]
+pub struct CidSetIter<'a> {
+    set: &'a CidSet,
+    pos: usize,
+}
+
+impl<'a> Iterator for CidSetIter<'a> {
+    type Item = CidRef<'a>;
+
+    fn next(&mut self) -> Option<CidRef<'a>> {
+        if self.pos == self.set.len() {
+            return None;
+        }
+        self.pos += 1;
+        Some(self.set.get(self.pos - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.set.len() - self.pos;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for CidSetIter<'_> {}
@@ -0,0 +1,59 @@
+//! sqlx integration.
+//!
+//! The impls are generic over the database. A CID is encoded as its bytes (`BYTEA`, `BLOB`),
+//! and decoded from either binary or text columns. To store it in a text column, bind its string
+//! representation instead.
+
+use core::convert::TryFrom;
+
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::{Database, Decode, Encode, Type, ValueRef};
+
+use crate::cid::Cid;
+
+impl<DB> Type<DB> for Cid
+where
+    DB: Database,
+    Vec<u8>: Type<DB>,
+    String: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <Vec<u8> as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <Vec<u8> as Type<DB>>::compatible(ty) || <String as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB> Encode<'q, DB> for Cid
+where
+    DB: Database,
+    Vec<u8>: Encode<'q, DB>,
+{
+    fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+        self.to_bytes().encode(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.encoded_len()
+    }
+}
+
+impl<'r, DB> Decode<'r, DB> for Cid
+where
+    DB: Database,
+    &'r [u8]: Decode<'r, DB>,
+    &'r str: Decode<'r, DB> + Type<DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let is_text = <&str as Type<DB>>::compatible(&value.type_info());
+        let cid = if is_text {
+            Cid::try_from(<&str as Decode<DB>>::decode(value)?)?
+        } else {
+            Cid::try_from(<&[u8] as Decode<DB>>::decode(value)?)?
+        };
+        Ok(cid)
+    }
+}
@@ -0,0 +1,42 @@
+//! Reading and writing CIDs with tokio's async I/O.
+//!
+//! A CID is read byte by byte until its multihash length is known, so that no byte after it is
+//! consumed, like [`Cid::read_bytes`] does. See the `futures-io` feature for the runtime-agnostic
+//! `futures` I/O traits.
+
+use std::io;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::cid::Cid;
+use crate::framing::{CidReader, Step};
+
+impl Cid {
+    /// Read exactly one encoded CID from an async reader.
+    ///
+    /// Invalid CIDs are reported as `InvalidData` errors, which wrap the [`Error`](crate::Error).
+    pub async fn read_async<R: AsyncRead + Unpin + ?Sized>(r: &mut R) -> io::Result<Cid> {
+        let mut reader = CidReader::new();
+        loop {
+            match reader.step() {
+                Step::Byte => reader.push(r.read_u8().await?)?,
+                Step::Digest => {
+                    r.read_exact(reader.digest_mut()).await?;
+                    return Ok(reader.finish()?);
+                }
+            }
+        }
+    }
+
+    /// Write the encoded bytes of the CID to an async writer.
+    ///
+    /// Returns the number of bytes written, which is always `encoded_len()`.
+    pub async fn write_async<W: AsyncWrite + Unpin + ?Sized>(
+        &self,
+        w: &mut W,
+    ) -> io::Result<usize> {
+        let bytes = self.to_bytes();
+        w.write_all(&bytes).await?;
+        Ok(bytes.len())
+    }
+}
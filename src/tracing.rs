@@ -0,0 +1,22 @@
+//! Recording CIDs as `tracing` span and event fields.
+//!
+//! `tracing` only accepts its own value types as fields, so a CID has to be recorded with its
+//! `Display` form, e.g. with the `%` sigil. [`Cid::as_field`] does the same for fields built
+//! without the macros, so that CIDs show up as strings in structured (e.g. JSON) logs rather than
+//! as debug structs.
+
+use tracing::field::{display, DisplayValue};
+
+use crate::cid::Cid;
+
+impl Cid {
+    /// Return the CID as a `tracing` field value, recorded as its string representation.
+    ///
+    /// ```
+    /// # let cid: cid::Cid = "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n".parse().unwrap();
+    /// tracing::info!(cid = cid.as_field(), "stored block");
+    /// ```
+    pub fn as_field(&self) -> DisplayValue<&Cid> {
+        display(self)
+    }
+}
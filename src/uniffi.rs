@@ -0,0 +1,64 @@
+//! Kotlin and Swift bindings, via UniFFI.
+//!
+//! [`Cid`] is exported as the `Cid` class, failures are reported as [`Error`] exception.
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+use crate::error::Error;
+
+/// A CID, exported to Kotlin and Swift.
+#[derive(PartialEq, Eq, Hash, Debug, uniffi::Object)]
+#[uniffi::export(Display, Eq, Hash)]
+pub struct Cid(crate::cid::Cid);
+
+#[uniffi::export]
+impl Cid {
+    /// Parse a CID from its string representation.
+    #[uniffi::constructor]
+    pub fn parse(cid: String) -> Result<Arc<Self>, Error> {
+        Ok(Arc::new(Cid(crate::cid::Cid::try_from(cid)?)))
+    }
+
+    /// Decode a CID from its encoded bytes.
+    #[uniffi::constructor]
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Arc<Self>, Error> {
+        Ok(Arc::new(Cid(crate::cid::Cid::try_from(bytes)?)))
+    }
+
+    /// The encoded bytes of the CID.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    /// The version of the CID.
+    pub fn version(&self) -> u64 {
+        self.0.version.into()
+    }
+
+    /// The multicodec code of the CID.
+    pub fn codec(&self) -> u64 {
+        self.0.codec.into()
+    }
+}
+
+impl fmt::Display for Cid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl From<crate::cid::Cid> for Cid {
+    fn from(cid: crate::cid::Cid) -> Self {
+        Cid(cid)
+    }
+}
+
+impl From<Cid> for crate::cid::Cid {
+    fn from(cid: Cid) -> Self {
+        cid.0
+    }
+}
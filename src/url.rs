@@ -0,0 +1,49 @@
+//! CID conversions from `url::Url`s.
+//!
+//! Path and subdomain gateway URLs are supported, as well as `ipfs://`, `ipns://` and `dweb:`
+//! URIs.
+
+use core::convert::TryFrom;
+
+use url::Url;
+
+use crate::cid::Cid;
+use crate::error::{Error, Result};
+use crate::path::IpfsPath;
+
+impl TryFrom<&Url> for IpfsPath {
+    type Error = Error;
+
+    fn try_from(url: &Url) -> Result<Self> {
+        match url.scheme() {
+            "http" | "https" => IpfsPath::from_gateway_url(url.as_str()),
+            "ipfs" | "ipns" | "dweb" => IpfsPath::from_uri(url.as_str()),
+            _ => Err(Error::InvalidUrl),
+        }
+    }
+}
+
+impl TryFrom<Url> for IpfsPath {
+    type Error = Error;
+
+    fn try_from(url: Url) -> Result<Self> {
+        IpfsPath::try_from(&url)
+    }
+}
+
+impl TryFrom<&Url> for Cid {
+    type Error = Error;
+
+    /// Return the CID of an IPFS URL, or `Error::InvalidPath` for an IPNS URL.
+    fn try_from(url: &Url) -> Result<Self> {
+        Cid::try_from(IpfsPath::try_from(url)?)
+    }
+}
+
+impl TryFrom<Url> for Cid {
+    type Error = Error;
+
+    fn try_from(url: Url) -> Result<Self> {
+        Cid::try_from(&url)
+    }
+}
@@ -0,0 +1,29 @@
+//! utoipa integration.
+//!
+//! A CID is described by its string representation, as used by human-readable serde formats.
+
+use alloc::borrow::Cow;
+
+use utoipa::openapi::schema::{ObjectBuilder, SchemaFormat, SchemaType, Type};
+use utoipa::openapi::{RefOr, Schema};
+use utoipa::{PartialSchema, ToSchema};
+
+use crate::cid::Cid;
+use crate::schema;
+
+impl PartialSchema for Cid {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(SchemaType::Type(Type::String))
+            .format(Some(SchemaFormat::Custom(schema::FORMAT.into())))
+            .pattern(Some(schema::PATTERN))
+            .description(Some(schema::DESCRIPTION))
+            .into()
+    }
+}
+
+impl ToSchema for Cid {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("Cid")
+    }
+}
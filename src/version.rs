@@ -1,7 +1,11 @@
 use crate::error::{Error, Result};
 
 /// The version of the CID.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+///
+/// Future CID versions may be added, so the enum is non-exhaustive.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum Version {
     /// CID version 0.
     V0,
@@ -12,12 +16,13 @@ pub enum Version {
 use Version::*;
 
 impl Version {
-    /// Convert a number to the matching version, or `Error` if no valid version is matching.
+    /// Convert a number to the matching version, or `Error::UnsupportedVersion` if no valid
+    /// version is matching.
     pub fn from(raw: u64) -> Result<Version> {
         match raw {
             0 => Ok(V0),
             1 => Ok(V1),
-            _ => Err(Error::InvalidCidVersion),
+            _ => Err(Error::UnsupportedVersion(raw)),
         }
     }
 
@@ -0,0 +1,72 @@
+//! JavaScript bindings, via wasm-bindgen.
+//!
+//! [`JsCid`] is exported to JavaScript as the `Cid` class.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use wasm_bindgen::prelude::*;
+
+use crate::cid::Cid;
+
+/// A CID, exported to JavaScript.
+#[wasm_bindgen(js_name = Cid)]
+#[derive(Clone, Debug)]
+pub struct JsCid(Cid);
+
+#[wasm_bindgen(js_class = Cid)]
+impl JsCid {
+    /// Parse a CID from its string representation.
+    pub fn parse(cid: &str) -> Result<JsCid, JsError> {
+        Ok(JsCid(Cid::try_from(cid)?))
+    }
+
+    /// Decode a CID from its encoded bytes.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<JsCid, JsError> {
+        Ok(JsCid(Cid::try_from(bytes)?))
+    }
+
+    /// The string representation of the CID.
+    #[wasm_bindgen(js_name = toString)]
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// The encoded bytes of the CID.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    /// The version of the CID.
+    #[wasm_bindgen(getter)]
+    pub fn version(&self) -> u8 {
+        u64::from(self.0.version) as u8
+    }
+
+    /// The multicodec code of the CID, as JavaScript number.
+    #[wasm_bindgen(getter)]
+    pub fn codec(&self) -> f64 {
+        u64::from(self.0.codec) as f64
+    }
+
+    /// Whether both CIDs are equal.
+    pub fn equals(&self, other: &JsCid) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl From<Cid> for JsCid {
+    fn from(cid: Cid) -> Self {
+        JsCid(cid)
+    }
+}
+
+impl From<JsCid> for Cid {
+    fn from(cid: JsCid) -> Self {
+        cid.0
+    }
+}
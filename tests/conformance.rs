@@ -0,0 +1,295 @@
+//! Conformance vectors in the shape of the go-cid and js-multiformats test suites.
+//!
+//! The expected strings, bytes and components were computed independently of this crate, with the
+//! reference hash functions and base encoders, so that a regression in either direction shows up
+//! as a mismatch with what the other implementations produce.
+
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use cid::Cid;
+
+/// A valid CID with its string, binary and component forms.
+struct Valid {
+    string: &'static str,
+    bytes: &'static str,
+    version: u64,
+    codec: u64,
+    hash: u64,
+    digest: &'static str,
+}
+
+const VALID: &[Valid] = &[
+    Valid {
+        string: "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n",
+        bytes: "1220e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        version: 0,
+        codec: 0x70,
+        hash: 0x12,
+        digest: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+    },
+    Valid {
+        string: "QmaozNR7DZHQK1ZcU9p7QdrshMvXqWK6gpu5rmrkPdT3L4",
+        bytes: "1220b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9",
+        version: 0,
+        codec: 0x70,
+        hash: 0x12,
+        digest: "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9",
+    },
+    Valid {
+        string: "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku",
+        bytes: "01701220e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        version: 1,
+        codec: 0x70,
+        hash: 0x12,
+        digest: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+    },
+    Valid {
+        string: "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e",
+        bytes: "01551220b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9",
+        version: 1,
+        codec: 0x55,
+        hash: 0x12,
+        digest: "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9",
+    },
+    Valid {
+        string: "BAFYREIFZJUT3TE2NHYEKKLSS27NH3K72YSCO7Y32KOAO5EEI66WOF36N5E",
+        bytes: "01711220b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9",
+        version: 1,
+        codec: 0x71,
+        hash: 0x12,
+        digest: "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9",
+    },
+    Valid {
+        string: "z25sX6LgHoZpCZUT3VEMjKbNPvMpQ2EU16DF2mM4vQDixfQGHLJ1cB",
+        bytes: "01a902a0e40220256c83b297114d201b30179f3f0ef0cace9783622da5974326b436178aeef610",
+        version: 1,
+        codec: 0x129,
+        hash: 0xb220,
+        digest: "256c83b297114d201b30179f3f0ef0cace9783622da5974326b436178aeef610",
+    },
+    Valid {
+        string: "k56om9dv6jyc1b",
+        bytes: "0155000568656c6c6f",
+        version: 1,
+        codec: 0x55,
+        hash: 0x0,
+        digest: "68656c6c6f",
+    },
+    Valid {
+        string: "f017811142aae6c35c94fcfb415dbe95f408b9ce91ee846ed",
+        bytes: "017811142aae6c35c94fcfb415dbe95f408b9ce91ee846ed",
+        version: 1,
+        codec: 0x78,
+        hash: 0x11,
+        digest: "2aae6c35c94fcfb415dbe95f408b9ce91ee846ed",
+    },
+    Valid {
+        string: "mAXATQDCezEicEtbrTMQPUMkC8rTQ7XfuURp8epvNPKhtTNhvmJ3TW8X/SZZw2jQlW0Wwz9gw6B9gXc99xVQuk66c128",
+        bytes: "01701340309ecc489c12d6eb4cc40f50c902f2b4d0ed77ee511a7c7a9bcd3ca86d4cd86f989dd35bc5ff499670da34255b45b0cfd830e81f605dcf7dc5542e93ae9cd76f",
+        version: 1,
+        codec: 0x70,
+        hash: 0x13,
+        digest: "309ecc489c12d6eb4cc40f50c902f2b4d0ed77ee511a7c7a9bcd3ca86d4cd86f989dd35bc5ff499670da34255b45b0cfd830e81f605dcf7dc5542e93ae9cd76f",
+    },
+    Valid {
+        string: "uAVUWIGRLzH5WQ3MECZmqyJ52IvPKcfuh2XL9lKMcO_vyTjk4",
+        bytes: "01551620644bcc7e564373040999aac89e7622f3ca71fba1d972fd94a31c3bfbf24e3938",
+        version: 1,
+        codec: 0x55,
+        hash: 0x16,
+        digest: "644bcc7e564373040999aac89e7622f3ca71fba1d972fd94a31c3bfbf24e3938",
+    },
+    Valid {
+        string: "z3g7rzbAPtNagM3b9TGNtAco5VEdZCGvrnjY",
+        bytes: "018180c0018080c00110000102030405060708090a0b0c0d0e0f",
+        version: 1,
+        codec: 0x300001,
+        hash: 0x300000,
+        digest: "000102030405060708090a0b0c0d0e0f",
+    },
+    Valid {
+        string: "k51qzi5uqu5dg6l7sg2ssb5uefnq8g7g1d6n6j2zsio0o0k7snyb11p8myhxxc",
+        bytes: "01720024080112200000000000000000000000000000000000000000000000000000000000000000",
+        version: 1,
+        codec: 0x72,
+        hash: 0x0,
+        digest: "080112200000000000000000000000000000000000000000000000000000000000000000",
+    },
+];
+/// Strings that no implementation accepts, with the reason.
+const INVALID_STRINGS: &[(&str, &str)] = &[
+    ("", "empty"),
+    ("Qm", "too short for a CIDv0"),
+    (
+        "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1",
+        "CIDv0 of 45 characters",
+    ),
+    (
+        "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR10",
+        "CIDv0 with a character outside base58btc",
+    ),
+    (
+        "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvy",
+        "truncated digest",
+    ),
+    (
+        "!afybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku",
+        "unknown multibase prefix",
+    ),
+    (
+        "bajybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku",
+        "CIDv2",
+    ),
+    (
+        "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvykuaa",
+        "trailing byte",
+    ),
+    ("z38dbd", "missing digest"),
+    ("mAXAAAA==", "padded base64"),
+];
+
+/// Binary CIDs that no implementation accepts, with the reason.
+const INVALID_BYTES: &[(&str, &str)] = &[
+    ("", "empty"),
+    ("1220e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b8", "CIDv0 with a short digest"),
+    ("1220e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b85500", "CIDv0 with a trailing byte"),
+    ("1221e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b85500", "CIDv0 with a 33 byte digest"),
+    ("02701220e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855", "CIDv2"),
+    ("01701220e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b8", "truncated digest"),
+    ("01701220e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b85500", "trailing byte"),
+    ("017012", "missing multihash length"),
+    ("01ffffffffffffffffffff011220e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855", "codec varint overflow"),
+];
+
+/// Non-canonical encodings that the spec rejects but some implementations accept, with the
+/// canonical string of the CID they decode to. The strict parsers reject them, the lenient ones
+/// read the CID.
+const NON_CANONICAL_STRINGS: &[(&str, &str, &str)] = &[
+    (
+        "babybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku",
+        "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n",
+        "explicit CIDv0 version",
+    ),
+    (
+        "bahyaaera4oymiquy7qobjgx36tejs35zeqt24qpemsnzgtfeswmrw6csxbkq",
+        "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku",
+        "non-minimal codec varint",
+    ),
+    (
+        "zQmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n",
+        "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n",
+        "CIDv0 with a multibase prefix",
+    ),
+    (
+        "bAFYBEIHDWDCEFGH4DQKJV67UZCMW7OJEE6XEDZDETOJUZJEVTENXQUVYKU",
+        "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku",
+        "uppercase base32 with a lowercase prefix",
+    ),
+];
+
+/// Binary counterparts of [`NON_CANONICAL_STRINGS`].
+const NON_CANONICAL_BYTES: &[(&str, &str, &str)] = &[
+    (
+        "00701220e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n",
+        "explicit CIDv0 version",
+    ),
+    (
+        "01f0001220e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku",
+        "non-minimal codec varint",
+    ),
+];
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn check(cid: &Cid, vector: &Valid) {
+    assert_eq!(u64::from(cid.version), vector.version, "{}", vector.string);
+    assert_eq!(u64::from(cid.codec), vector.codec, "{}", vector.string);
+    assert_eq!(
+        cid.hash.algorithm().to_u64(),
+        vector.hash,
+        "{}",
+        vector.string
+    );
+    assert_eq!(
+        cid.hash.digest(),
+        &unhex(vector.digest)[..],
+        "{}",
+        vector.string
+    );
+    assert_eq!(cid.to_bytes(), unhex(vector.bytes), "{}", vector.string);
+}
+
+#[test]
+fn valid_strings() {
+    for vector in VALID {
+        let cid = Cid::from_str(vector.string).unwrap();
+        check(&cid, vector);
+        assert_eq!(cid.to_string(), vector.string);
+        assert_eq!(cid.to_string_of_base(cid.base()).unwrap(), vector.string);
+        assert_eq!(Cid::from_str_strict(vector.string).unwrap(), cid);
+    }
+}
+
+#[test]
+fn valid_bytes() {
+    for vector in VALID {
+        let cid = Cid::try_from(unhex(vector.bytes)).unwrap();
+        check(&cid, vector);
+        assert_eq!(cid, Cid::from_str(vector.string).unwrap());
+        assert_eq!(Cid::from_bytes_strict(&unhex(vector.bytes)).unwrap(), cid);
+    }
+}
+
+#[test]
+fn invalid_strings() {
+    for (string, reason) in INVALID_STRINGS {
+        let err = Cid::from_str(string).expect_err(reason);
+        assert!(
+            err.is_parse() || err.is_unsupported(),
+            "{}: {:?}",
+            reason,
+            err
+        );
+    }
+}
+
+#[test]
+fn invalid_bytes() {
+    for (bytes, reason) in INVALID_BYTES {
+        let err = Cid::try_from(unhex(bytes)).expect_err(reason);
+        assert!(
+            err.is_parse() || err.is_unsupported(),
+            "{}: {:?}",
+            reason,
+            err
+        );
+    }
+}
+
+#[test]
+fn non_canonical_strings() {
+    for (string, canonical, reason) in NON_CANONICAL_STRINGS {
+        let err = Cid::from_str_strict(string).expect_err(reason);
+        assert!(err.is_parse(), "{}: {:?}", reason, err);
+        let cid = Cid::from_str(string).unwrap();
+        assert_eq!(cid, Cid::from_str_strict(canonical).unwrap(), "{}", reason);
+    }
+}
+
+#[test]
+fn non_canonical_bytes() {
+    for (bytes, canonical, reason) in NON_CANONICAL_BYTES {
+        let err = Cid::from_bytes_strict(&unhex(bytes)).expect_err(reason);
+        assert!(err.is_parse(), "{}: {:?}", reason, err);
+        let cid = Cid::try_from(unhex(bytes)).unwrap();
+        assert_eq!(cid, Cid::from_str_strict(canonical).unwrap(), "{}", reason);
+    }
+}
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::str::FromStr;
 
-use cid::{Cid, Codec, Error, Prefix, Version};
+use cid::{Base, Cause, Cid, CidRef, Codec, Component, Error, Limits, Prefix, Version};
 use multihash::Sha2_256;
 
 #[test]
@@ -15,8 +15,9 @@ fn basic_marshalling() {
     let out = Cid::try_from(data.clone()).unwrap();
     assert_eq!(cid, out);
 
-    let out2: Cid = data.try_into().unwrap();
+    let out2: Cid = data.clone().try_into().unwrap();
     assert_eq!(cid, out2);
+    assert_eq!(Vec::from(out2), data);
 
     let s = cid.to_string();
     let out3 = Cid::try_from(&s[..]).unwrap();
@@ -48,13 +49,27 @@ fn from_str() {
     assert_eq!(cid.version, Version::V0);
 
     let bad = "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zIII".parse::<Cid>();
-    assert_eq!(bad, Err(Error::ParsingError));
+    assert!(matches!(
+        bad,
+        Err(Error::Malformed {
+            component: Component::Multibase,
+            offset: 43,
+            source: Some(Cause::Multibase(_)),
+        })
+    ));
 }
 
 #[test]
 fn v0_error() {
     let bad = "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zIII";
-    assert_eq!(Cid::try_from(bad), Err(Error::ParsingError));
+    assert!(matches!(
+        Cid::try_from(bad),
+        Err(Error::Malformed {
+            component: Component::Multibase,
+            offset: 43,
+            source: Some(Cause::Multibase(_)),
+        })
+    ));
 }
 
 #[test]
@@ -65,7 +80,7 @@ fn prefix_roundtrip() {
     let cid = Cid::new_v1(Codec::DagProtobuf, h);
     let prefix = cid.prefix();
 
-    let cid2 = Cid::new_from_prefix(&prefix, data);
+    let cid2 = Cid::new_from_prefix(&prefix, data).unwrap();
 
     assert_eq!(cid, cid2);
 
@@ -102,11 +117,257 @@ fn test_hash() {
         mh_len: 32,
     };
     let mut map = HashMap::new();
-    let cid = Cid::new_from_prefix(&prefix, &data);
+    let cid = Cid::new_from_prefix(&prefix, &data).unwrap();
     map.insert(cid.clone(), data.clone());
     assert_eq!(&data, map.get(&cid).unwrap());
 }
 
+#[test]
+fn hash_identity() {
+    use std::collections::HashSet;
+
+    let short = multihash::wrap(multihash::Code::Identity, b"a");
+    let empty = multihash::wrap(multihash::Code::Identity, b"");
+    let cids = vec![
+        Cid::new_v1(Codec::Raw, short.clone()),
+        Cid::new_v1(Codec::DagCBOR, short),
+        Cid::new_v1(Codec::Raw, empty.clone()),
+        Cid::new_v1(Codec::DagCBOR, empty),
+    ];
+
+    let set: HashSet<_> = cids.iter().cloned().collect();
+    assert_eq!(set.len(), cids.len());
+    for cid in &cids {
+        assert!(set.contains(cid));
+        assert!(set.contains(&Cid::try_from(cid.to_bytes()).unwrap()));
+    }
+}
+
+#[test]
+fn hash_matches_cid_ref() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let v1 = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo"));
+    let v0 = Cid::new_v0(Sha2_256::digest(b"foo")).unwrap();
+    for cid in &[v1, v0] {
+        let bytes = cid.to_bytes();
+        assert_eq!(hash(cid), hash(&CidRef::from_bytes(&bytes).unwrap()));
+    }
+}
+
+#[test]
+fn borrowed_lookup() {
+    use std::collections::{BTreeMap, HashSet};
+
+    use cid::CidKey;
+
+    let v1 = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo"));
+    let v0 = Cid::new_v0(Sha2_256::digest(b"foo")).unwrap();
+    let other = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"bar"));
+    let set: HashSet<Cid> = vec![v1.clone(), v0.clone()].into_iter().collect();
+    let map: BTreeMap<Cid, u8> = vec![(v1.clone(), 1), (v0.clone(), 0)].into_iter().collect();
+    for (cid, value) in &[(v1, Some(&1)), (v0, Some(&0)), (other, None)] {
+        let bytes = cid.to_bytes();
+        let key = CidRef::from_bytes(&bytes).unwrap();
+        assert_eq!(set.contains(&key as &dyn CidKey), value.is_some());
+        assert_eq!(map.get(&key as &dyn CidKey), *value);
+    }
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn hashbrown_equivalent() {
+    let cid = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo"));
+    let bytes = cid.to_bytes();
+    let mut map = hashbrown::HashMap::with_hasher(std::collections::hash_map::RandomState::new());
+    map.insert(cid, "foo");
+    assert_eq!(map.get(&CidRef::from_bytes(&bytes).unwrap()), Some(&"foo"));
+}
+
+#[cfg(feature = "hasher")]
+#[test]
+fn cid_set() {
+    use std::collections::HashSet;
+
+    use cid::CidSet;
+
+    fn to_std(set: &CidSet) -> HashSet<Cid> {
+        set.iter().map(|cid| cid.to_owned()).collect()
+    }
+
+    // A corpus may repeat short identity multihashes, so compare with a `HashSet`.
+    let cids: Vec<Cid> = cid::corpus(0, 1000).collect();
+    let mut set = CidSet::new();
+    let mut expected = HashSet::new();
+    for cid in &cids[..600] {
+        assert_eq!(set.insert(cid), expected.insert(cid.clone()));
+        assert!(set.contains(cid));
+    }
+    assert_eq!(set.len(), expected.len());
+    assert_eq!(to_std(&set), expected);
+    assert!(cids[600..]
+        .iter()
+        .all(|cid| set.contains(cid) == expected.contains(cid)));
+    let bytes = cids[0].to_bytes();
+    assert!(set.contains(&CidRef::from_bytes(&bytes).unwrap()));
+    assert_eq!(set.iter().next().unwrap(), cids[0]);
+
+    let other: CidSet = cids[400..].iter().cloned().collect();
+    let other_expected = to_std(&other);
+    assert_eq!(to_std(&set.union(&other)), &expected | &other_expected);
+    assert_eq!(
+        to_std(&set.intersection(&other)),
+        &expected & &other_expected
+    );
+    assert_eq!(to_std(&set.difference(&other)), &expected - &other_expected);
+    assert_eq!(set.union(&other), other.union(&set));
+    assert!(set.difference(&set).is_empty());
+}
+
+#[cfg(feature = "hasher")]
+#[test]
+fn cid_index() {
+    use cid::CidIndex;
+
+    let cids: Vec<Cid> = cid::corpus(0, 1000).collect();
+    let index: CidIndex = cids.iter().chain(&cids[..100]).cloned().collect();
+    let mut sorted: Vec<Vec<u8>> = cids.iter().map(Cid::to_bytes).collect();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(index.len(), sorted.len());
+    let iterated: Vec<Vec<u8>> = index.iter().map(|cid| cid.to_bytes()).collect();
+    assert_eq!(iterated, sorted);
+    for (pos, bytes) in sorted.iter().enumerate() {
+        let cid = CidRef::from_bytes(bytes).unwrap();
+        assert_eq!(index.position(&cid), Some(pos));
+        assert_eq!(index.get(pos).unwrap(), cid);
+    }
+    assert_eq!(index.get(sorted.len()), None);
+    assert!(cid::corpus(1, 100).all(|cid| !index.contains(&cid)));
+    assert!(!CidIndex::new().contains(&cids[0]));
+
+    let bytes = index.to_bytes();
+    assert!(bytes.len() < sorted.iter().map(Vec::len).sum());
+    assert_eq!(CidIndex::from_bytes(&bytes).unwrap(), index);
+    let mut written = Vec::new();
+    index.write_to(&mut written).unwrap();
+    assert_eq!(written, bytes);
+    assert_eq!(CidIndex::read_from(&written[..]).unwrap(), index);
+    assert_eq!(
+        CidIndex::from_bytes(&CidIndex::new().to_bytes()).unwrap(),
+        CidIndex::new()
+    );
+
+    assert_eq!(CidIndex::from_bytes(b"CIDY"), Err(Error::InvalidIndex));
+    assert_eq!(
+        CidIndex::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(Error::InvalidIndex)
+    );
+    // Two CIDs stored in full, in the wrong order.
+    let mut unsorted = b"CIDX\x01\x10\x02".to_vec();
+    for cid in &[&sorted[1], &sorted[0]] {
+        unsorted.extend_from_slice(&[0, cid.len() as u8]);
+        unsorted.extend_from_slice(cid);
+    }
+    assert_eq!(CidIndex::from_bytes(&unsorted), Err(Error::InvalidIndex));
+    unsorted[7..].rotate_left(sorted[1].len() + 2);
+    assert!(CidIndex::from_bytes(&unsorted).is_ok());
+    let err = CidIndex::read_from(&b"CIDX\x01"[..]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[cfg(all(feature = "bloom", feature = "hasher"))]
+#[test]
+fn cid_bloom() {
+    use cid::CidBloom;
+
+    let cids: Vec<Cid> = cid::corpus(0, 1000).collect();
+    let mut bloom = CidBloom::new(1000, 0.01);
+    assert_eq!(bloom.num_bits(), 9600);
+    assert_eq!(bloom.num_hashes(), 7);
+    for cid in &cids {
+        bloom.insert(cid);
+    }
+    assert!(cids.iter().all(|cid| bloom.contains(cid)));
+    let bytes = cids[0].to_bytes();
+    assert!(bloom.contains(&CidRef::from_bytes(&bytes).unwrap()));
+    let false_positives = cid::corpus(1, 10_000)
+        .filter(|cid| bloom.contains(cid))
+        .count();
+    assert!(false_positives < 200, "{} false positives", false_positives);
+
+    let bytes = bloom.to_bytes();
+    assert_eq!(bytes.len(), 4 + 1 + 1 + 2 + 9600 / 8);
+    assert_eq!(CidBloom::from_bytes(&bytes).unwrap(), bloom);
+    let mut written = Vec::new();
+    bloom.write_to(&mut written).unwrap();
+    assert_eq!(CidBloom::read_from(&written[..]).unwrap(), bloom);
+    assert_eq!(
+        CidBloom::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(Error::InvalidBloomFilter)
+    );
+    assert_eq!(
+        CidBloom::from_bytes(b"CIDX\x01\x07\x40"),
+        Err(Error::InvalidBloomFilter)
+    );
+
+    // Identity digests that share their first 16 bytes are mixed, so they don't collide.
+    let identity = |i: u8| {
+        let mut data = [0; 32];
+        data[31] = i;
+        Cid::new_v1(
+            Codec::Raw,
+            multihash::wrap(multihash::Code::Identity, &data),
+        )
+    };
+    let mut ids = CidBloom::new(100, 0.01);
+    for i in 0..100 {
+        ids.insert(&identity(i));
+    }
+    let false_positives = (100..=255).filter(|&i| ids.contains(&identity(i))).count();
+    assert!(false_positives < 10, "{} false positives", false_positives);
+
+    bloom.clear();
+    assert!(!cids.iter().any(|cid| bloom.contains(cid)));
+    assert!(bloom.insert(&cids[0]));
+    assert!(!bloom.insert(&cids[0]));
+}
+
+#[test]
+fn ordering() {
+    use std::collections::BTreeSet;
+
+    let foo = Sha2_256::digest(b"foo");
+    let bar = Sha2_256::digest(b"bar");
+    let (low, high) = if foo.as_bytes() < bar.as_bytes() {
+        (foo, bar)
+    } else {
+        (bar, foo)
+    };
+
+    let expected = vec![
+        Cid::new_v0(high.clone()).unwrap(),
+        Cid::new_v1(Codec::Raw, high.clone()),
+        Cid::new_v1(Codec::DagProtobuf, low.clone()),
+        Cid::new_v1(Codec::DagProtobuf, high.clone()),
+        Cid::new_v1(Codec::DagJSON, low),
+    ];
+    let set: BTreeSet<_> = expected.iter().rev().cloned().collect();
+    assert_eq!(set.into_iter().collect::<Vec<_>>(), expected);
+
+    // The base a CID was parsed from doesn't matter.
+    let cid = Cid::new_v1(Codec::Raw, high);
+    let base58 = Cid::try_from(cid.to_string_of_base(Base::Base58Btc).unwrap()).unwrap();
+    assert_eq!(cid.cmp(&base58), std::cmp::Ordering::Equal);
+}
+
 #[test]
 fn test_base32() {
     let cid = Cid::from_str("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
@@ -114,3 +375,2133 @@ fn test_base32() {
     assert_eq!(cid.codec, Codec::Raw);
     assert_eq!(cid.hash, Sha2_256::digest(b"foo"));
 }
+
+#[test]
+fn digest() {
+    let cid = Cid::from_str("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+    let digest = Sha2_256::digest(b"foo");
+    assert_eq!(cid.digest(), digest.digest());
+    assert_eq!(cid.digest().len(), 32);
+
+    let bytes = cid.to_bytes();
+    assert_eq!(CidRef::from_bytes(&bytes).unwrap().digest(), cid.digest());
+}
+
+#[test]
+fn cid_ref() {
+    let h = Sha2_256::digest(b"beep boop");
+    let cid = Cid::new_v1(Codec::DagCBOR, h.clone());
+    let bytes = cid.to_bytes();
+
+    let cid_ref = CidRef::try_from(bytes.as_slice()).unwrap();
+    assert_eq!(cid_ref.version(), Version::V1);
+    assert_eq!(cid_ref.codec(), Codec::DagCBOR);
+    assert_eq!(cid_ref.hash(), h);
+    assert_eq!(cid_ref.as_bytes(), bytes.as_slice());
+    assert_eq!(cid_ref, cid);
+    assert_eq!(cid_ref.to_owned(), cid);
+
+    let v0 = Cid::new_v0(h).unwrap();
+    let v0_bytes = v0.to_bytes();
+    let v0_ref = CidRef::from_bytes(&v0_bytes).unwrap();
+    assert_eq!(v0_ref.version(), Version::V0);
+    assert_eq!(v0_ref, v0);
+    assert!(v0_ref < cid_ref);
+    assert!(v0_ref < cid);
+    assert!(cid > v0_ref);
+}
+
+#[test]
+fn cached_cid() {
+    use cid::CachedCid;
+
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+    let cached = CachedCid::from(cid.clone());
+
+    assert_eq!(cached.as_str(), cid.to_string());
+    assert!(std::ptr::eq(cached.as_str(), cached.as_str()));
+    assert_eq!(cached.to_string(), cid.to_string());
+    assert_eq!(cached.as_bytes(), &cid.to_bytes()[..]);
+    assert!(std::ptr::eq(cached.as_bytes(), cached.as_bytes()));
+
+    assert_eq!(cached.codec, Codec::Raw);
+    assert_eq!(cached, cid);
+    assert_eq!(cached.clone().into_inner(), cid);
+}
+
+#[test]
+fn interner() {
+    use cid::CidInterner;
+    use std::sync::Arc;
+
+    let interner = CidInterner::new();
+    let foo = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo"));
+    let bar = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"bar"));
+
+    let a = interner.intern(&foo);
+    let b = interner.intern(&foo.clone());
+    let c = interner.intern(&bar);
+    assert!(Arc::ptr_eq(a.as_arc(), b.as_arc()));
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_eq!(*a, foo);
+    assert_eq!(interner.len(), 2);
+    assert_eq!(interner.get(&bar), Some(c.clone()));
+
+    drop(c);
+    interner.purge();
+    assert_eq!(interner.len(), 1);
+    assert_eq!(interner.get(&bar), None);
+    assert!(interner.get(&foo).is_some());
+}
+
+#[test]
+fn cid_n() {
+    use cid::CidN;
+
+    let v1 = Cid::new_v1(Codec::DagCBOR, Sha2_256::digest(b"beep boop"));
+    let bytes = v1.to_bytes();
+
+    let inline = CidN::<32>::from_bytes(&bytes).unwrap();
+    let copy = inline;
+    assert_eq!(copy, inline);
+    assert_eq!(inline, v1);
+    assert_eq!(inline.version(), Version::V1);
+    assert_eq!(inline.codec(), Codec::DagCBOR);
+    assert_eq!(inline.hash_code(), multihash::Code::Sha2_256);
+    assert_eq!(inline.digest(), v1.hash.digest());
+    assert_eq!(inline.to_bytes(), bytes);
+    assert_eq!(inline.encoded_len(), bytes.len());
+    assert_eq!(inline.to_string(), v1.to_string());
+    assert_eq!(inline.to_string().parse::<CidN<32>>().unwrap(), inline);
+    assert_eq!(Cid::from(inline), v1);
+
+    let v0 = Cid::new_v0(Sha2_256::digest(b"beep boop")).unwrap();
+    let inline_v0 = CidN::<64>::try_from(&v0).unwrap();
+    assert_eq!(inline_v0.to_bytes(), v0.to_bytes());
+    assert_eq!(inline_v0.to_string(), v0.to_string());
+    assert!(inline_v0 < CidN::<64>::try_from(&v1).unwrap());
+
+    assert_eq!(CidN::<16>::from_bytes(&bytes), Err(Error::DigestTooLarge));
+
+    let sha512 = Cid::new_v1(Codec::DagCBOR, multihash::Sha2_512::digest(b"beep boop"));
+    let inline = CidN::<64>::try_from(&sha512).unwrap();
+    assert_eq!(inline.digest(), sha512.hash.digest());
+    assert_eq!(Cid::from(inline), sha512);
+    assert_eq!(CidN::<32>::try_from(&sha512), Err(Error::DigestTooLarge));
+}
+
+#[test]
+fn cid_n_const() {
+    use cid::CidN;
+
+    const DIGEST: [u8; 32] = [
+        0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14, 0x9a, 0xfb, 0xf4, 0xc8, 0x99, 0x6f, 0xb9,
+        0x24, 0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c, 0xa4, 0x95, 0x99, 0x1b, 0x78, 0x52,
+        0xb8, 0x55,
+    ];
+    const V0: CidN<32> = CidN::from_v0_digest(DIGEST);
+    const V1: CidN<32> = CidN::from_v1_digest(Codec::DagProtobuf, 0x12, DIGEST);
+    const VERSION: Version = V0.version();
+    const CODEC: Codec = V1.codec();
+    static PARSED: CidN<32> = CidN::from_bytes_const(&[
+        0x01, 0x70, 0x12, 0x20, 0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14, 0x9a, 0xfb, 0xf4,
+        0xc8, 0x99, 0x6f, 0xb9, 0x24, 0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c, 0xa4, 0x95,
+        0x99, 0x1b, 0x78, 0x52, 0xb8, 0x55,
+    ]);
+
+    assert_eq!(
+        V0.to_string(),
+        "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n"
+    );
+    assert_eq!(
+        V1.to_string(),
+        "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+    );
+    assert_eq!((VERSION, CODEC), (Version::V0, Codec::DagProtobuf));
+    assert_eq!(PARSED, V1);
+    assert_eq!(CidN::<32>::from_bytes_const(&V0.to_bytes()), V0);
+    assert_eq!(
+        CidN::<64>::from_bytes_const(&V1.to_bytes()).digest(),
+        V1.digest()
+    );
+
+    let mut non_minimal = V1.to_bytes();
+    non_minimal.splice(1..2, [0xf0, 0x00]);
+    let mut trailing = V1.to_bytes();
+    trailing.push(0);
+    let mut v2 = V1.to_bytes();
+    v2[0] = 2;
+    for bytes in [non_minimal, trailing, v2, V1.to_bytes()[..20].to_vec()] {
+        assert!(std::panic::catch_unwind(|| CidN::<32>::from_bytes_const(&bytes)).is_err());
+    }
+    assert!(std::panic::catch_unwind(|| CidN::<16>::from_bytes_const(&V1.to_bytes())).is_err());
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn cid_macro() {
+    use cid::{cid, CidN};
+
+    macro_rules! wrapped {
+        ($cid:expr) => {
+            cid!($cid)
+        };
+    }
+
+    const V0: CidN<32> = cid!("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n");
+    static V1: CidN<32> = cid!("bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku");
+    const INLINE: CidN<5> = cid!("k56om9dv6jyc1b");
+    const WRAPPED: CidN<32> =
+        wrapped!("bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku");
+
+    assert_eq!(
+        V0.to_string(),
+        "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n"
+    );
+    assert_eq!(
+        V1.to_string(),
+        "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+    );
+    assert_eq!(V1.digest(), V0.digest());
+    assert_eq!(INLINE.digest(), b"hello");
+    assert_eq!(INLINE.codec(), Codec::Raw);
+    assert_eq!(WRAPPED, V1);
+}
+
+#[test]
+fn unknown_codec() {
+    let codec = Codec::Other(0x0300_0000);
+    let cid = Cid::new_v1(codec, Sha2_256::digest(b"foo"));
+    assert!(!cid.codec.is_known());
+
+    let out = Cid::try_from(cid.to_bytes()).unwrap();
+    assert_eq!(out.codec, codec);
+    assert_eq!(out.to_bytes(), cid.to_bytes());
+    let out = Cid::try_from(cid.to_string()).unwrap();
+    assert_eq!(out, cid);
+    assert_eq!(
+        Prefix::new_from_bytes(&cid.prefix().as_bytes())
+            .unwrap()
+            .codec,
+        codec
+    );
+
+    assert_eq!(Codec::from(0x0300_0000), Err(Error::UnknownCodec));
+    assert_eq!(Codec::from_code(0x55), Codec::Raw);
+    assert_eq!(Codec::Other(0x55), Codec::Raw);
+    assert!(Codec::Other(0x55).is_known());
+}
+
+#[test]
+fn codec_names() {
+    assert_eq!(Codec::DagCBOR.name(), Some("dag-cbor"));
+    assert_eq!(Codec::EthereumTxReceipt.name(), Some("eth-tx-receipt"));
+    assert_eq!(Codec::Other(0x0300_0000).name(), None);
+
+    assert_eq!(Codec::from_name("raw"), Some(Codec::Raw));
+    assert_eq!(Codec::from_name("dag-jose"), Some(Codec::DagJOSE));
+    assert_eq!(u64::from(Codec::from_name("libp2p-key").unwrap()), 0x72);
+    // Only content codecs of the table are included, not multihashes.
+    assert_eq!(Codec::from_name("sha2-256"), None);
+}
+
+#[test]
+fn codec_display_from_str() {
+    assert_eq!(Codec::DagCBOR.to_string(), "dag-cbor");
+    assert_eq!("dag-cbor".parse::<Codec>(), Ok(Codec::DagCBOR));
+    assert_eq!("raw".parse::<Codec>(), Ok(Codec::Raw));
+    assert_eq!("0x71".parse::<Codec>(), Ok(Codec::DagCBOR));
+    assert_eq!("DAG-CBOR".parse::<Codec>(), Err(Error::UnknownCodec));
+    assert_eq!("0xzz".parse::<Codec>(), Err(Error::UnknownCodec));
+
+    let codec = Codec::Other(0x0300_0000);
+    assert_eq!(codec.to_string(), "0x3000000");
+    assert_eq!(codec.to_string().parse::<Codec>(), Ok(codec));
+}
+
+#[test]
+fn filecoin_and_ceramic_codecs() {
+    for (codec, code) in [
+        (Codec::DagJSON, 0x0129),
+        (Codec::DagJOSE, 0x85),
+        (Codec::Libp2pKey, 0x72),
+        (Codec::GitRaw, 0x78),
+        (Codec::FilCommitmentUnsealed, 0xf101),
+        (Codec::FilCommitmentSealed, 0xf102),
+    ] {
+        assert_eq!(u64::from(codec), code);
+        assert!(codec.is_known());
+    }
+
+    let cid = Cid::new_v1(Codec::FilCommitmentUnsealed, Sha2_256::digest(b"foo"));
+    let out = Cid::try_from(cid.to_string()).unwrap();
+    assert_eq!(out.codec, Codec::FilCommitmentUnsealed);
+    assert_eq!(out.codec.to_string(), "fil-commitment-unsealed");
+}
+
+#[test]
+fn peer_key_multihash() {
+    let hash = multihash::wrap(multihash::Code::Identity, b"public key");
+    let cid = Cid::from_peer_key_multihash(hash.clone()).unwrap();
+    assert_eq!(cid.version, Version::V1);
+    assert_eq!(cid.codec, Codec::Libp2pKey);
+    assert_eq!(cid.try_into_peer_multihash(), Ok(hash));
+
+    let hash = Sha2_256::digest(b"public key");
+    let cid = Cid::from_peer_key_multihash(hash.clone()).unwrap();
+    assert_eq!(cid.try_into_peer_multihash(), Ok(hash));
+
+    assert_eq!(
+        Cid::from_peer_key_multihash(multihash::Sha2_512::digest(b"public key")),
+        Err(Error::InvalidPeerId)
+    );
+    let cid = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"public key"));
+    assert_eq!(cid.try_into_peer_multihash(), Err(Error::InvalidPeerId));
+}
+
+#[test]
+fn inline() {
+    let cid = Cid::new_inline(Codec::DagCBOR, b"small node").unwrap();
+    assert_eq!(cid.hash.algorithm(), multihash::Code::Identity);
+    assert_eq!(cid.inline_data(), Some(&b"small node"[..]));
+
+    let bytes = cid.to_bytes();
+    let cid_ref = CidRef::from_bytes(&bytes).unwrap();
+    assert_eq!(cid_ref.inline_data(), Some(&b"small node"[..]));
+    assert_eq!(Cid::try_from(cid.to_string()).unwrap(), cid);
+
+    let data = [0u8; 33];
+    assert_eq!(
+        Cid::new_inline(Codec::Raw, &data),
+        Err(Error::InlineDataTooLarge)
+    );
+    let cid = Cid::new_inline_with_max_len(Codec::Raw, &data, 64).unwrap();
+    assert_eq!(cid.inline_data(), Some(&data[..]));
+
+    let cid = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo"));
+    assert_eq!(cid.inline_data(), None);
+}
+
+#[test]
+fn parse_limits() {
+    let cid = Cid::new_inline_with_max_len(Codec::Raw, &[0; 129], 129).unwrap();
+    assert_eq!(Cid::try_from(cid.to_bytes()), Err(Error::DigestTooLarge));
+    assert_eq!(Cid::try_from(cid.to_string()), Err(Error::DigestTooLarge));
+    let limits = Limits {
+        max_digest_len: 129,
+        ..Limits::DEFAULT
+    };
+    assert_eq!(
+        Cid::from_bytes_with_limits(&cid.to_bytes(), &limits),
+        Ok(cid.clone())
+    );
+    assert_eq!(
+        Cid::from_str_with_limits(&cid.to_string(), &limits),
+        Ok(cid)
+    );
+
+    let cid = Cid::new_inline_with_max_len(Codec::Raw, &[0; 1024], 1024).unwrap();
+    assert_eq!(Cid::try_from(cid.to_bytes()), Err(Error::InputTooLong));
+    assert_eq!(
+        CidRef::from_bytes(&cid.to_bytes()).unwrap_err(),
+        Error::InputTooLong
+    );
+    assert_eq!(Cid::try_from(cid.to_string()), Err(Error::InputTooLong));
+    assert_eq!(
+        Cid::from_bytes_with_limits(&cid.to_bytes(), &Limits::NONE),
+        Ok(cid)
+    );
+
+    let long = format!("b{}", "a".repeat(10_000));
+    assert_eq!(Cid::try_from(long.as_str()), Err(Error::InputTooLong));
+}
+
+#[test]
+fn strict_parsing() {
+    let v0 = Cid::new_v0(Sha2_256::digest(b"foo")).unwrap();
+    let v1 = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo"));
+    assert_eq!(Cid::from_bytes_strict(&v0.to_bytes()), Ok(v0.clone()));
+    assert_eq!(Cid::from_bytes_strict(&v1.to_bytes()), Ok(v1.clone()));
+    assert_eq!(Cid::from_str_strict(&v0.to_string()), Ok(v0.clone()));
+    assert_eq!(Cid::from_str_strict(&v1.to_string()), Ok(v1.clone()));
+
+    // Codec 0x55 encoded in two bytes.
+    let mut bytes = vec![0x01, 0xd5, 0x00];
+    bytes.extend_from_slice(&v1.hash);
+    assert_eq!(Cid::try_from(bytes.as_slice()), Ok(v1.clone()));
+    assert_eq!(Cid::from_bytes_strict(&bytes), Err(Error::NonMinimalVarint));
+    let canonical = v1.to_bytes();
+    assert_eq!(
+        CidRef::from_bytes(&bytes).unwrap(),
+        CidRef::from_bytes(&canonical).unwrap()
+    );
+
+    let mut bytes = v1.to_bytes();
+    bytes.push(0x00);
+    assert!(Cid::try_from(bytes.as_slice()).is_err());
+    assert_eq!(CidRef::from_bytes_strict(&bytes), Err(Error::TrailingBytes));
+
+    let mut bytes = vec![0x00, 0x70];
+    bytes.extend_from_slice(&v0.hash);
+    assert_eq!(Cid::try_from(bytes.as_slice()), Ok(v0.clone()));
+    assert_eq!(
+        Cid::from_bytes_strict(&bytes),
+        Err(Error::InvalidCidVersion)
+    );
+
+    let padded = v1.to_string_of_base(Base::Base32PadLower).unwrap();
+    assert_eq!(Cid::from_str_strict(&padded), Err(Error::PaddedMultibase));
+
+    let upper = format!("b{}", v1.to_string()[1..].to_ascii_uppercase());
+    assert_eq!(Cid::try_from(upper.as_str()), Ok(v1.clone()));
+    assert!(Cid::from_str_strict(&upper).is_err());
+
+    let path = format!("/ipfs/{}", v1);
+    assert!(Cid::from_str_strict(&path).is_err());
+
+    let v0_str = v0.to_string();
+    assert_eq!(
+        Cid::from_str_strict(&v0_str[..45]),
+        Err(Error::InvalidCidV0String)
+    );
+    let v0_multibase = multibase::encode(Base::Base58Btc, v0.to_bytes());
+    assert_eq!(Cid::try_from(v0_multibase.as_str()), Ok(v0));
+    assert_eq!(
+        Cid::from_str_strict(&v0_multibase),
+        Err(Error::InvalidCidV0String)
+    );
+}
+
+#[test]
+fn custom_multihash_code() {
+    // A code from the multicodec private-use range, unknown to `multihash`.
+    let code = multihash::Code::Custom(0x30_0000);
+    let cid = Cid::new_v1(Codec::Raw, multihash::wrap(code, b"private digest"));
+    assert_eq!(cid.hash.algorithm(), code);
+
+    let out = Cid::try_from(cid.to_bytes()).unwrap();
+    assert_eq!(cid, out);
+    let out = Cid::try_from(cid.to_string()).unwrap();
+    assert_eq!(cid, out);
+
+    let prefix = cid.prefix();
+    assert_eq!(prefix.mh_type, code);
+    assert_eq!(Prefix::new_from_bytes(&prefix.as_bytes()).unwrap(), prefix);
+    assert_eq!(
+        Cid::new_from_prefix(&prefix, b"data"),
+        Err(Error::UnsupportedMultihash)
+    );
+}
+
+#[test]
+fn cid_generic() {
+    use cid::{CidGeneric, CodeTable};
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    struct Private;
+
+    impl CodeTable for Private {
+        fn from_code(code: u64) -> Option<Self> {
+            if code == 0x30_0000 {
+                Some(Private)
+            } else {
+                None
+            }
+        }
+
+        fn code(&self) -> u64 {
+            0x30_0000
+        }
+    }
+
+    let cid = CidGeneric::new_v1(Codec::DagCBOR, Private, b"private digest");
+    assert_eq!(cid.digest(), b"private digest");
+    let bytes = cid.to_bytes();
+    assert_eq!(bytes.len(), cid.encoded_len());
+    assert_eq!(CidGeneric::<Private>::from_bytes(&bytes).unwrap(), cid);
+    assert_eq!(cid.to_string().parse::<CidGeneric<Private>>().unwrap(), cid);
+
+    let owned = Cid::try_from(bytes.as_slice()).unwrap();
+    assert_eq!(owned, cid);
+    assert_eq!(cid, owned);
+    assert_eq!(Cid::from(cid.clone()), owned);
+    assert_eq!(owned.to_string(), cid.to_string());
+    assert_eq!(CidGeneric::<Private>::try_from(&owned).unwrap(), cid);
+
+    let sha2 = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo"));
+    assert_eq!(
+        CidGeneric::<Private>::from_bytes(&sha2.to_bytes()),
+        Err(Error::UnsupportedMultihash)
+    );
+    assert_eq!(
+        CidGeneric::<Private>::new_v0(&[0; 32]),
+        Err(Error::UnsupportedMultihash)
+    );
+
+    // `multihash::Code` accepts every code, like `Cid`.
+    let v0 = Cid::new_v0(Sha2_256::digest(b"foo")).unwrap();
+    let generic = CidGeneric::<multihash::Code>::try_from(&v0).unwrap();
+    assert_eq!(generic, v0);
+    assert_eq!(generic.to_bytes(), v0.to_bytes());
+    assert_eq!(generic.to_string(), v0.to_string());
+    assert_eq!(
+        CidGeneric::<multihash::Code>::new_v0(v0.hash.digest()).unwrap(),
+        generic
+    );
+}
+
+#[test]
+fn v1_string_is_base32() {
+    let v0 = Cid::try_from("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n").unwrap();
+    let v1 = Cid::new_v1(v0.codec, v0.hash);
+
+    let s = "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku";
+    assert_eq!(v1.to_string(), s);
+    assert_eq!(Cid::try_from(s).unwrap(), v1);
+}
+
+#[test]
+fn builder() {
+    use cid::CidBuilder;
+    use multihash::Code;
+
+    let cid = CidBuilder::new().build(b"foo").unwrap();
+    assert_eq!(cid, Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo")));
+
+    let cid = CidBuilder::new()
+        .codec(Codec::DagCBOR)
+        .hash_with(Code::Blake2b256)
+        .build(b"foo")
+        .unwrap();
+    assert_eq!(cid.codec, Codec::DagCBOR);
+    assert_eq!(cid.hash, multihash::Blake2b256::digest(b"foo"));
+
+    let v0 = CidBuilder::new()
+        .version(Version::V0)
+        .build(b"foo")
+        .unwrap();
+    assert_eq!(v0, Cid::new_v0(Sha2_256::digest(b"foo")).unwrap());
+
+    let truncated = CidBuilder::new().hash_len(16).build(b"foo").unwrap();
+    assert_eq!(truncated.digest(), &Sha2_256::digest(b"foo").digest()[..16]);
+
+    let invalid = CidBuilder::new().version(Version::V0).codec(Codec::Raw);
+    assert_eq!(invalid.validate(), Err(Error::InvalidCidV0Codec));
+    let invalid = CidBuilder::new().version(Version::V0).hash_len(16);
+    assert_eq!(invalid.build(b"foo"), Err(Error::InvalidCidV0Multihash));
+    let invalid = CidBuilder::new().hash_with(Code::Custom(0x30_0000));
+    assert_eq!(invalid.build(b"foo"), Err(Error::UnsupportedMultihash));
+}
+
+#[test]
+fn parts() {
+    let cid = Cid::new_v1(Codec::DagCBOR, Sha2_256::digest(b"foo"));
+    let (version, codec, hash) = cid.clone().into_parts();
+    assert_eq!(version, Version::V1);
+    assert_eq!(codec, Codec::DagCBOR);
+    assert_eq!(hash, Sha2_256::digest(b"foo"));
+
+    assert_eq!(Cid::from_parts(version, codec, hash.clone()).unwrap(), cid);
+    let raw = Cid::from_parts(version, Codec::Raw, hash.clone()).unwrap();
+    assert_eq!(raw, Cid::new_v1(Codec::Raw, hash.clone()));
+    assert_eq!(
+        Cid::from_parts(Version::V0, Codec::Raw, hash),
+        Err(Error::InvalidCidV0Codec)
+    );
+}
+
+#[test]
+fn unchecked() {
+    let v1 = Cid::new_v1(Codec::DagCBOR, Sha2_256::digest(b"foo"));
+    assert_eq!(Cid::from_bytes_unchecked(&v1.to_bytes()), v1);
+
+    let v0 = Cid::new_v0(Sha2_256::digest(b"foo")).unwrap();
+    assert_eq!(Cid::from_bytes_unchecked(&v0.to_bytes()), v0);
+    assert_eq!(Cid::new_v0_unchecked(Sha2_256::digest(b"foo")), v0);
+    assert_eq!(Cid::new_v1_unchecked(Codec::DagCBOR, &v1.hash), v1);
+}
+
+#[test]
+fn malformed_offsets() {
+    fn location<T>(result: Result<T, Error>) -> Option<(Component, usize)> {
+        match result {
+            Err(Error::Malformed {
+                component, offset, ..
+            }) => Some((component, offset)),
+            _ => None,
+        }
+    }
+    let malformed = |component, offset| Some((component, offset));
+    let cid = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo"));
+    let bytes = cid.to_bytes();
+
+    assert_eq!(
+        location(Cid::try_from(&[0x80][..])),
+        malformed(Component::Version, 0)
+    );
+    assert_eq!(
+        location(Cid::try_from(&[0x01, 0xff][..])),
+        malformed(Component::Codec, 1)
+    );
+    assert_eq!(
+        location(Cid::try_from(&bytes[..2])),
+        malformed(Component::Multihash, 2)
+    );
+    assert_eq!(
+        location(Cid::try_from(&bytes[..20])),
+        malformed(Component::Multihash, 20)
+    );
+    let mut trailing = bytes.clone();
+    trailing.push(0x00);
+    assert_eq!(
+        location(Cid::try_from(trailing.as_slice())),
+        malformed(Component::Multihash, 36)
+    );
+
+    assert_eq!(
+        location(Cid::try_from("!foo")),
+        malformed(Component::Multibase, 0)
+    );
+    let string = cid.to_string();
+    let bad = format!("/ipfs/{}!{}", &string[..10], &string[11..]);
+    assert_eq!(
+        location(Cid::try_from(bad.as_str())),
+        malformed(Component::Multibase, 16)
+    );
+    assert_eq!(
+        Cid::try_from(&bad[6..]).unwrap_err().to_string(),
+        "Invalid multibase encoding at character 10"
+    );
+    assert_eq!(
+        Cid::try_from(&bytes[..20]).unwrap_err().to_string(),
+        "Invalid multihash at byte 20"
+    );
+}
+
+#[test]
+fn error_categories() {
+    use std::error::Error as _;
+
+    let err = Cid::try_from("bai").unwrap_err();
+    assert!(err.is_unsupported());
+    assert!(!err.is_parse());
+
+    let err = Cid::try_from("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zIII").unwrap_err();
+    assert!(err.is_parse());
+    assert!(!err.is_unsupported());
+
+    let err = Error::from(multibase::Base::from_code('!').unwrap_err());
+    assert!(err.is_parse());
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        multibase::Error::UnknownBase('!').to_string()
+    );
+    let err = Error::from(unsigned_varint::decode::u64(&[0x80]).unwrap_err());
+    assert!(err.source().is_some());
+    assert!(Error::InputTooShort.source().is_none());
+
+    // The parsers keep the error of the underlying decoder as the source.
+    let err = Cid::try_from("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zIII").unwrap_err();
+    assert!(err.source().is_some());
+    let err = Cid::from_str_strict("!foo").unwrap_err();
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        multibase::Error::UnknownBase('!').to_string()
+    );
+    let err = Cid::try_from(&[0x01, 0xff][..]).unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Malformed {
+            source: Some(Cause::VarInt(_)),
+            ..
+        }
+    ));
+    assert!(err.source().is_some());
+}
+
+#[test]
+fn io_error() {
+    fn read(bytes: &[u8]) -> std::io::Result<Cid> {
+        Ok(Cid::try_from(bytes)?)
+    }
+
+    let err = read(&[0x02]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    let inner = err.into_inner().unwrap().downcast::<Error>().unwrap();
+    assert_eq!(*inner, Error::UnsupportedVersion(2));
+}
+
+#[test]
+fn read_bytes() {
+    let v0 = Cid::new_v0(Sha2_256::digest(b"foo")).unwrap();
+    let v1 = Cid::new_v1(Codec::DagCBOR, multihash::Sha2_512::digest(b"foo"));
+    let mut buf = v1.to_bytes();
+    buf.extend_from_slice(&v0.to_bytes());
+    buf.extend_from_slice(b"rest");
+
+    let mut reader = std::io::Cursor::new(&buf);
+    assert_eq!(Cid::read_bytes(&mut reader).unwrap(), v1);
+    assert_eq!(Cid::read_bytes(&mut reader).unwrap(), v0);
+    assert_eq!(&buf[reader.position() as usize..], b"rest");
+
+    let bytes = v1.to_bytes();
+    let err = Cid::read_bytes(&bytes[..bytes.len() - 1]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    let err = Cid::read_bytes(&[0x02, 0x55, 0x12, 0x01, 0x00][..]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn decode_with_len() {
+    let v0 = Cid::new_v0(Sha2_256::digest(b"foo")).unwrap();
+    let v1 = Cid::new_v1(Codec::DagCBOR, multihash::Sha2_512::digest(b"foo"));
+    let mut buf = v1.to_bytes();
+    buf.extend_from_slice(&v0.to_bytes());
+    buf.extend_from_slice(b"rest");
+
+    let (cid, len) = Cid::decode(&buf).unwrap();
+    assert_eq!(cid, v1);
+    assert_eq!(len, v1.encoded_len());
+    let (cid, len2) = CidRef::decode(&buf[len..]).unwrap();
+    assert_eq!(cid, v0);
+    assert_eq!(&buf[len + len2..], b"rest");
+
+    let bytes = v1.to_bytes();
+    assert!(matches!(
+        Cid::decode(&bytes[..bytes.len() - 1]),
+        Err(Error::Malformed {
+            component: Component::Multihash,
+            offset,
+            ..
+        }) if offset == bytes.len() - 1
+    ));
+    assert!(Cid::decode(&v0.to_bytes()[..33]).unwrap_err().is_parse());
+}
+
+#[test]
+fn cid_seq() {
+    use cid::{CidSeqReader, CidSeqWriter};
+
+    let cids = vec![
+        Cid::new_v0(Sha2_256::digest(b"foo")).unwrap(),
+        Cid::new_v1(Codec::DagCBOR, multihash::Sha2_512::digest(b"foo")),
+        Cid::new_inline(Codec::Raw, b"bar").unwrap(),
+    ];
+    let mut writer = CidSeqWriter::new(Vec::new());
+    for cid in &cids {
+        writer.write(cid).unwrap();
+    }
+    let bytes = writer.into_inner();
+    assert_eq!(bytes[0] as usize, cids[0].encoded_len());
+
+    let read: Vec<Cid> = CidSeqReader::new(bytes.as_slice())
+        .collect::<std::io::Result<_>>()
+        .unwrap();
+    assert_eq!(read, cids);
+
+    let mut reader = CidSeqReader::new(&bytes[..bytes.len() - 1]);
+    assert!(reader.next().unwrap().is_ok());
+    assert!(reader.next().unwrap().is_ok());
+    let err = reader.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    let mut reader = CidSeqReader::new(&[0x80, 0x08][..]);
+    let err = reader.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(CidSeqReader::new(&[][..]).next().is_none());
+    let mut reader = CidSeqReader::new(&[0x80][..]);
+    let err = reader.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    /// Reads one byte at a time, interrupted before each.
+    struct Interrupting<'a>(&'a [u8], bool);
+
+    impl std::io::Read for Interrupting<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.1 = !self.1;
+            if self.1 {
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+            let len = buf.len().min(self.0.len()).min(1);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    let read: Vec<Cid> = CidSeqReader::new(Interrupting(&bytes, false))
+        .collect::<std::io::Result<_>>()
+        .unwrap();
+    assert_eq!(read, cids);
+}
+
+#[test]
+fn parse_lines() {
+    use cid::CidLinesExt;
+
+    let a = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"a"));
+    let b = Cid::new_v1(Codec::DagCBOR, Sha2_256::digest(b"b"));
+    let input = format!(
+        "# pins\n\n  {}  \n{} # recursive\n\t\n   # indented comment\nfoo\n",
+        a, b
+    );
+
+    let cids: Vec<_> = input.lines().parse_cids().collect();
+    assert_eq!(cids.len(), 3);
+    assert_eq!(cids[0], Ok(a.clone()));
+    assert_eq!(cids[1], Ok(b.clone()));
+    assert!(cids[2].is_err());
+
+    let mut lines = cid::parse_lines(input.as_bytes());
+    assert_eq!(lines.next().unwrap().unwrap(), a);
+    assert_eq!(lines.next().unwrap().unwrap(), b);
+    let err = lines.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(lines.next().is_none());
+}
+
+#[test]
+fn scan() {
+    let v0 = "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n";
+    let v1 = Cid::new_v1(Codec::DagCBOR, Sha2_256::digest(b"foo"));
+    let v1_str = v1.to_string();
+    let text = format!(
+        "<a href=\"/ipfs/{}/x\">é</a> {{\"cid\":\"{}\"}} bad {}x {}",
+        v0,
+        v1_str,
+        v1_str,
+        v1.to_string_of_base(Base::Base36Lower).unwrap()
+    );
+
+    let found: Vec<_> = cid::scan(&text).collect();
+    assert_eq!(found.len(), 3);
+    assert_eq!(&text[found[0].0.clone()], v0);
+    assert_eq!(found[0].1, Cid::try_from(v0).unwrap());
+    assert_eq!(&text[found[1].0.clone()], v1_str);
+    assert_eq!(found[1].1, v1);
+    assert_eq!(found[2].1, v1);
+    assert_eq!(found[2].0.end, text.len());
+
+    assert_eq!(cid::scan("no cids here, bafy").count(), 0);
+}
+
+#[test]
+fn ipfs_path() {
+    use cid::{IpfsPath, PathRoot};
+
+    let v0 = "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n";
+    let path: IpfsPath = format!("/ipfs/{}/a//b/", v0).parse().unwrap();
+    let cid = Cid::try_from(v0).unwrap();
+    assert_eq!(path.root, PathRoot::Ipfs(cid.clone()));
+    assert_eq!(path.segments, ["a", "b"]);
+    assert_eq!(path.remainder(), "a/b");
+    assert_eq!(path.to_string(), format!("/ipfs/{}/a/b", v0));
+    assert_eq!(Cid::try_from(&path), Ok(cid.clone()));
+    assert_eq!(Cid::try_from(path), Ok(cid.clone()));
+    assert_eq!(
+        IpfsPath::from(cid.clone()).to_string(),
+        format!("/ipfs/{}", v0)
+    );
+
+    let path = IpfsPath::try_from(
+        "/ipns/k51qzi5uqu5dlvj2baxnqndepeb86cbk3ng7n3i46uzyxzyqj2xjonzllnv0v8/x",
+    )
+    .unwrap();
+    assert!(path.cid().is_none());
+    assert_eq!(path.segments, ["x"]);
+    assert_eq!(Cid::try_from(path), Err(Error::InvalidPath));
+
+    for invalid in &[
+        "",
+        "/",
+        "/ipfs",
+        "/ipfs/",
+        "ipfs/QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n",
+        "/foo/bar",
+    ] {
+        assert_eq!(
+            invalid.parse::<IpfsPath>(),
+            Err(Error::InvalidPath),
+            "{}",
+            invalid
+        );
+    }
+    assert!("/ipfs/foo".parse::<IpfsPath>().unwrap_err().is_parse());
+}
+
+#[test]
+fn gateway_url() {
+    use cid::{IpfsPath, PathRoot};
+
+    let v1 = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    let cid = Cid::try_from(v1).unwrap();
+    let ipfs = |url: &str| IpfsPath::from_gateway_url(url).unwrap();
+
+    let path = ipfs(&format!("https://{}.ipfs.dweb.link/a/b?filename=x#top", v1));
+    assert_eq!(path.root, PathRoot::Ipfs(cid.clone()));
+    assert_eq!(path.segments, ["a", "b"]);
+    let path = ipfs(&format!("http://{}.IPFS.localhost:8080", v1.to_uppercase()));
+    assert_eq!(path.root, PathRoot::Ipfs(cid.clone()));
+    assert!(path.segments.is_empty());
+    let path = ipfs(&format!("https://gateway.ipfs.io/ipfs/{}/a", v1));
+    assert_eq!(path.root, PathRoot::Ipfs(cid.clone()));
+    assert_eq!(path.segments, ["a"]);
+
+    let key = "k51qzi5uqu5dlvj2baxnqndepeb86cbk3ng7n3i46uzyxzyqj2xjonzllnv0v8";
+    let path = ipfs(&format!("https://{}.ipns.example.com/x", key));
+    assert_eq!(path.root, PathRoot::Ipns(key.to_string()));
+    assert_eq!(path.segments, ["x"]);
+    let path = ipfs("https://en-wikipedia--on--ipfs-org.ipns.dweb.link/wiki/");
+    assert_eq!(
+        path.root,
+        PathRoot::Ipns("en.wikipedia-on-ipfs.org".to_string())
+    );
+    let path = ipfs("https://user@ipfs.io:443/ipns/docs.ipfs.tech/");
+    assert_eq!(path.root, PathRoot::Ipns("docs.ipfs.tech".to_string()));
+    // A path gateway whose host merely contains `.ipns.`.
+    let path = ipfs(&format!("https://x.ipns.example.com/ipfs/{}/a", v1));
+    assert_eq!(path.root, PathRoot::Ipfs(cid.clone()));
+    assert_eq!(path.segments, ["a"]);
+    let path = ipfs("https://gateway.ipns.example.com/ipns/docs.ipfs.tech");
+    assert_eq!(path.root, PathRoot::Ipns("docs.ipfs.tech".to_string()));
+
+    for invalid in &[
+        "ftp://ipfs.io/ipfs/x",
+        "https://example.com/",
+        "https://ipfs.io/foo/bar",
+    ] {
+        assert_eq!(IpfsPath::from_gateway_url(invalid), Err(Error::InvalidUrl));
+    }
+}
+
+#[test]
+fn to_gateway_url() {
+    use cid::{GatewayStyle, IpfsPath};
+
+    let v1 = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    let cid = Cid::try_from(v1).unwrap();
+    assert_eq!(
+        cid.to_gateway_url("ipfs.io", GatewayStyle::Path),
+        format!("https://ipfs.io/ipfs/{}", v1)
+    );
+    let url = cid.to_gateway_url("dweb.link", GatewayStyle::Subdomain);
+    assert_eq!(url, format!("https://{}.ipfs.dweb.link/", v1));
+    assert_eq!(IpfsPath::from_gateway_url(&url).unwrap().cid(), Some(&cid));
+
+    // An Ed25519 key is 65 characters in base32.
+    let key = "k51qzi5uqu5dlvj2baxnqndepeb86cbk3ng7n3i46uzyxzyqj2xjonzllnv0v8";
+    let cid = Cid::try_from(key).unwrap();
+    assert_eq!(cid.to_subdomain_label(), key);
+
+    let cid = Cid::new_v1(Codec::Raw, multihash::Sha2_256::digest(b"foo"));
+    assert!(cid.to_subdomain_label().starts_with('b'));
+}
+
+#[test]
+fn ipfs_uri() {
+    use cid::{IpfsPath, PathRoot};
+
+    let v0 = "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n";
+    let v1 = "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku";
+    let cid = Cid::try_from(v1).unwrap();
+
+    let path = IpfsPath::from_uri(&format!("ipfs://{}/a/b?x#y", v1)).unwrap();
+    assert_eq!(path.cid(), Some(&cid));
+    assert_eq!(path.remainder(), "a/b");
+    assert_eq!(path.to_uri(), format!("ipfs://{}/a/b", v1));
+    let upper = IpfsPath::from_uri(&format!("ipfs://{}", v1.to_uppercase())).unwrap();
+    assert_eq!(upper.cid(), Some(&cid));
+
+    // CIDv0 can't be in the authority, as it's case-sensitive.
+    let path = IpfsPath::from_uri(&format!("dweb:/ipfs/{}/a", v0)).unwrap();
+    assert_eq!(path.cid(), Some(&Cid::try_from(v0).unwrap()));
+    assert_eq!(path.to_dweb_uri(), format!("dweb:/ipfs/{}/a", v0));
+    assert_eq!(path.to_uri(), format!("ipfs://{}/a", v1));
+
+    let path = IpfsPath::from_uri("ipns://docs.ipfs.tech/x").unwrap();
+    assert_eq!(path.root, PathRoot::Ipns("docs.ipfs.tech".to_string()));
+    assert_eq!(path.to_uri(), "ipns://docs.ipfs.tech/x");
+    assert_eq!(path.to_dweb_uri(), "dweb:/ipns/docs.ipfs.tech/x");
+
+    for invalid in &["ipns://", "https://ipfs.io/ipfs/x", "dweb:/foo", "ipfs:/x"] {
+        assert_eq!(
+            IpfsPath::from_uri(invalid),
+            Err(Error::InvalidUrl),
+            "{}",
+            invalid
+        );
+    }
+    assert!(IpfsPath::from_uri("ipfs://foo").unwrap_err().is_parse());
+}
+
+#[test]
+fn dnslink() {
+    use cid::{IpfsPath, PathRoot};
+
+    let v1 = "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku";
+    let path = IpfsPath::from_dnslink(&format!(" dnslink=/ipfs/{}/site\n", v1)).unwrap();
+    assert_eq!(path.cid(), Some(&Cid::try_from(v1).unwrap()));
+    assert_eq!(path.segments, ["site"]);
+    assert_eq!(path.to_dnslink(), format!("dnslink=/ipfs/{}/site", v1));
+
+    let path = IpfsPath::from_dnslink("dnslink=/ipns/example.com").unwrap();
+    assert_eq!(path.root, PathRoot::Ipns("example.com".to_string()));
+
+    for invalid in &[
+        "",
+        "/ipfs/x",
+        "dnslink=",
+        "dnslink=ipfs/x",
+        "dnslink=/foo/bar",
+        "v=spf1",
+    ] {
+        assert_eq!(
+            IpfsPath::from_dnslink(invalid),
+            Err(Error::InvalidDnsLink),
+            "{}",
+            invalid
+        );
+    }
+    assert!(IpfsPath::from_dnslink("dnslink=/ipfs/foo")
+        .unwrap_err()
+        .is_parse());
+}
+
+#[test]
+fn dshelp() {
+    use cid::dshelp;
+
+    let v0 = Cid::try_from("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n").unwrap();
+    let key = dshelp::cid_to_ds_key(&v0);
+    assert_eq!(
+        key,
+        "/CIQOHMGEIKMPYHAUTL57JSEZN64SIJ5OIHSGJG4TJSSJLGI3PBJLQVI"
+    );
+    assert_eq!(dshelp::cid_to_block_key(&v0), format!("/blocks{}", key));
+    // The key is the same for any CID with the same multihash.
+    assert_eq!(dshelp::cid_to_ds_key(&v0.clone().into_v1()), key);
+
+    assert_eq!(dshelp::ds_key_to_multihash(&key), Ok(v0.hash.clone()));
+    let cid = dshelp::ds_key_to_cid(&format!("/blocks{}", key), Codec::Raw).unwrap();
+    assert_eq!(cid, Cid::new_v1(Codec::Raw, v0.hash.clone()));
+
+    for invalid in &["", "CIQ", "/blocks", "/blocks/CIQ/x", "/foo/CIQ"] {
+        assert_eq!(
+            dshelp::ds_key_to_multihash(invalid),
+            Err(Error::InvalidDatastoreKey)
+        );
+    }
+    assert!(dshelp::ds_key_to_multihash("/ciq").unwrap_err().is_parse());
+}
+
+#[test]
+fn safe_filename() {
+    let v0 = Cid::try_from("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n").unwrap();
+    let name = v0.to_safe_filename();
+    assert_eq!(
+        name,
+        "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+    );
+    let v1 = v0.into_v1();
+    assert_eq!(Cid::from_safe_filename(&name), Ok(v1.clone()));
+    assert_eq!(
+        Cid::from_safe_filename(&format!("{}.data", name)),
+        Ok(v1.clone())
+    );
+    assert_eq!(
+        Cid::from_safe_filename(&format!("{}.block.tmp", name.to_uppercase())),
+        Ok(v1.clone())
+    );
+
+    let cid = Cid::try_from("zb2rhe5P4gXftAwvA4eXQ5HJwsER2owDyS9sKaQRRVQPn93bA").unwrap();
+    assert_eq!(Cid::from_safe_filename(&cid.to_safe_filename()), Ok(cid));
+    assert!(Cid::from_safe_filename(".block").is_err());
+}
+
+#[test]
+fn short() {
+    let cid = Cid::try_from("bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku").unwrap();
+    assert_eq!(cid.short(), "bafybei…vyku");
+    let cid = Cid::try_from("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n").unwrap();
+    assert_eq!(cid.short(), "QmdfTbB…zR1n");
+    let cid = Cid::new_inline(Codec::Raw, b"").unwrap();
+    assert_eq!(cid.short(), cid.to_string());
+}
+
+#[cfg(feature = "hasher")]
+#[test]
+fn corpus() {
+    let golden = [
+        "bafkrgqgt63hyszpg6kullyxex65qopyqg6pizycvx7kqrff5qiici5voswtcl27tjhzbamf7zgj53cmlgyt75j7njg5qjjm675d6naxneuahe",
+        "baguqeeiu5kvu6nsq7j2o23ldv6a7lk7nb3qe5g3f",
+        "bafzaahvlqaozpfz7xocfkessovoiekl5qz7x6kyqc7h4gzcpsgb2b2i",
+        "bafyreiewvrkdy6hsfy2ovbenmjpgupr2bklpvrhum6qolwgptv42uh4auu",
+        "baguqeeiu5ezgmzfprlzd6pivmz46hkn3mdzv6k4j",
+        "bafk4bzacidyq5cf3krsae4lwf2d5znzgnpnnxdgbb7iu6huiueorgicfoohhpx3edrie4mgp6ff2wy6oiqkfyty3oejt3c6fbah7zhluraemznfa",
+        "QmNzef8ZcCzEmryVRjpEZHSvJkg87MaV1mJ8yM4ErYbot8",
+        "baguqeaam4hzkdmoxcvdhpwssj5hq",
+    ];
+    let corpus: Vec<String> = cid::corpus(0, golden.len())
+        .map(|cid| cid.to_string())
+        .collect();
+    assert_eq!(corpus, golden);
+
+    let corpus = cid::corpus(1, 1000);
+    assert_eq!(corpus.len(), 1000);
+    let cids: Vec<Cid> = corpus.collect();
+    assert!(cids.iter().any(|cid| cid.version == Version::V0));
+    assert!(cids
+        .iter()
+        .any(|cid| cid.hash.algorithm() == multihash::Code::Identity));
+    assert!(cids.iter().any(|cid| cid.hash.digest().len() == 64));
+    for cid in &cids {
+        assert_eq!(Cid::try_from(cid.to_bytes()).as_ref(), Ok(cid));
+    }
+    assert_ne!(cids[..10], cid::corpus(2, 10).collect::<Vec<_>>()[..]);
+}
+
+#[test]
+fn display_alternate() {
+    let cid = Cid::try_from("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n").unwrap();
+    assert_eq!(
+        format!("{:#}", cid),
+        "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n
+  version: 0
+  codec: dag-pb (0x70)
+  multihash: sha2-256 (0x12)
+  digest length: 32 bytes
+  digest: e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+
+    let hash = multihash::wrap(multihash::Code::Custom(0x300000), &[0xab]);
+    let cid = Cid::new_v1(Codec::Other(0x300001), hash);
+    assert!(format!("{:#}", cid).ends_with(
+        "\n  version: 1
+  codec: 0x300001
+  multihash: 0x300000
+  digest length: 1 bytes
+  digest: ab"
+    ));
+}
+
+#[test]
+fn display_padding() {
+    use cid::{CachedCid, CidN};
+
+    let v0 = "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n";
+    let cid = Cid::try_from(v0).unwrap();
+    assert_eq!(format!("{:>50}", cid), format!("    {}", v0));
+    assert_eq!(format!("{:<48}|", cid), format!("{}  |", v0));
+    assert_eq!(format!("{:*^50}", cid), format!("**{}**", v0));
+    assert_eq!(format!("{:10}", cid), v0);
+
+    let cid_n = CidN::<64>::try_from(&cid).unwrap();
+    assert_eq!(format!("{:>50}", cid_n), format!("    {}", v0));
+    let cached = CachedCid::from(cid);
+    assert_eq!(format!("{:>50}", cached), format!("    {}", v0));
+
+    let cid = Cid::try_from(v0).unwrap();
+    assert_eq!(format!("{:é>48}", cid), format!("éé{}", v0));
+}
+
+#[test]
+fn hex() {
+    let cid = Cid::try_from("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n").unwrap();
+    let hex = "1220e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+    assert_eq!(format!("{:x}", cid), hex);
+    assert_eq!(format!("{:X}", cid), hex.to_uppercase());
+    assert_eq!(format!("{:#x}", cid), format!("0x{}", hex));
+
+    let cid = cid.into_v1();
+    assert_eq!(format!("{:x}", cid), format!("0170{}", hex));
+    assert_eq!(
+        format!("f{:x}", cid),
+        cid.to_string_of_base(Base::Base16Lower).unwrap()
+    );
+    assert_eq!(format!("{:>75x}", cid), format!("   0170{}", hex));
+}
+
+#[test]
+fn eq_str_and_bytes() {
+    let v1_str = "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku";
+    let v0_str = "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n";
+    let v1 = Cid::try_from(v1_str).unwrap();
+    let v0 = Cid::try_from(v0_str).unwrap();
+
+    assert!(v1 == v1_str);
+    assert!(v1_str == v1);
+    assert!(v1 == *v1_str);
+    assert!(v0 == v0_str);
+    assert!(v1 != v0_str);
+    assert!(v0 != v1_str);
+    let upper = v1.to_string_of_base(Base::Base32Upper).unwrap();
+    assert!(v1 == upper.as_str());
+    let base58 = v1.to_string_of_base(Base::Base58Btc).unwrap();
+    assert!(v1 == base58.as_str());
+    assert!(v1 != v1_str[..v1_str.len() - 1]);
+    assert!(v1 != format!("{}a", v1_str).as_str());
+    assert!(v1 != "");
+    assert!(v1 != "!afybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku");
+
+    let bytes = v1.to_bytes();
+    assert!(v1 == bytes[..]);
+    assert!(bytes[..] == v1);
+    assert!(v1 == bytes.as_slice());
+    assert!(v0 == v0.to_bytes()[..]);
+    assert!(v1 != v0.to_bytes()[..]);
+    assert!(v1 != bytes[..bytes.len() - 1]);
+}
+
+#[test]
+fn debug() {
+    let cid = Cid::try_from("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n").unwrap();
+    assert_eq!(
+        format!("{:?}", cid),
+        r#"Cid("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n", dag-pb, sha2-256)"#
+    );
+
+    let hash = multihash::wrap(multihash::Code::Custom(0x300000), &[0xab]);
+    let cid = Cid::new_v1(Codec::Other(0x300001), hash);
+    assert_eq!(
+        format!("{:?}", cid),
+        format!(r#"Cid("{}", 0x300001, 0x300000)"#, cid)
+    );
+}
+
+#[cfg(feature = "hasher")]
+#[test]
+fn verify() {
+    use cid::CidBuilder;
+
+    let mismatch = |res: Result<(), Error>| matches!(res, Err(Error::DigestMismatch(_)));
+
+    let cid = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo"));
+    assert_eq!(cid.verify(b"foo"), Ok(()));
+    assert!(mismatch(cid.verify(b"bar")));
+
+    let cid = Cid::new_inline(Codec::Raw, b"foo").unwrap();
+    assert_eq!(cid.verify(b"foo"), Ok(()));
+    assert!(mismatch(cid.verify(b"fo")));
+    assert!(mismatch(cid.verify(b"foox")));
+
+    let cid = CidBuilder::new().hash_len(20).build(b"foo").unwrap();
+    assert_eq!(cid.verify(b"foo"), Ok(()));
+    assert!(mismatch(cid.verify(b"bar")));
+
+    let hash = multihash::wrap(multihash::Code::Custom(0x300000), &[0; 32]);
+    let cid = Cid::new_v1(Codec::Raw, hash);
+    assert_eq!(cid.verify(b"foo"), Err(Error::UnsupportedMultihash));
+}
+
+#[test]
+fn hash() {
+    let cid = Cid::hash(Codec::DagCBOR, multihash::Code::Blake2b256, b"foo").unwrap();
+    assert_eq!(cid.version, Version::V1);
+    assert_eq!(cid.codec, Codec::DagCBOR);
+    assert_eq!(cid.hash, multihash::Blake2b256::digest(b"foo"));
+    assert_eq!(
+        Cid::hash(Codec::Raw, multihash::Code::Custom(0x300000), b"foo"),
+        Err(Error::UnsupportedMultihash)
+    );
+}
+
+#[cfg(feature = "hasher")]
+#[test]
+fn from_reader() {
+    let data = vec![7; 100_000];
+    for buf_size in &[0, 1, 1000, 1 << 20] {
+        let cid =
+            Cid::from_reader(Codec::Raw, multihash::Code::Sha2_256, &data[..], *buf_size).unwrap();
+        assert_eq!(cid, Cid::new_v1(Codec::Raw, Sha2_256::digest(&data)));
+    }
+
+    let cid = Cid::from_reader(Codec::Raw, multihash::Code::Identity, &b"foo"[..], 2).unwrap();
+    assert_eq!(cid.inline_data(), Some(&b"foo"[..]));
+    let err = Cid::from_reader(Codec::Raw, multihash::Code::Identity, &data[..], 8).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    let err =
+        Cid::from_reader(Codec::Raw, multihash::Code::Custom(0x300000), &data[..], 8).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[cfg(feature = "hasher")]
+#[test]
+fn verify_reader() {
+    let data = vec![7; 100_000];
+    for code in &[
+        multihash::Code::Sha1,
+        multihash::Code::Sha2_256,
+        multihash::Code::Sha3_512,
+        multihash::Code::Keccak256,
+        multihash::Code::Blake2b256,
+        multihash::Code::Blake2s128,
+    ] {
+        let cid = Cid::new_v1(Codec::Raw, code.hasher().unwrap().digest(&data));
+        assert_eq!(cid.verify(&data), Ok(()));
+        cid.verify_reader(&data[..]).unwrap();
+        let err = cid.verify_reader(&data[1..]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    let cid = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo"));
+    let err = cid.verify_reader(&b"bar"[..]).unwrap_err();
+    let err = err.into_inner().unwrap().downcast::<Error>().unwrap();
+    match *err {
+        Error::DigestMismatch(ref mismatch) => {
+            assert_eq!(mismatch.expected, cid.hash);
+            assert_eq!(mismatch.actual, Sha2_256::digest(b"bar"));
+        }
+        ref err => panic!("unexpected error: {:?}", err),
+    }
+    assert_eq!(
+        err.to_string(),
+        "Data does not match the CID digest: expected \
+         2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae, got \
+         fcde2b2edba56bf408601fb721fe9b5c338d10ee429ea04fae5511b68fbf8fb9"
+    );
+
+    let cid = Cid::new_inline(Codec::Raw, b"foo").unwrap();
+    cid.verify_reader(&b"foo"[..]).unwrap();
+    assert!(cid.verify_reader(std::io::repeat(0)).is_err());
+}
+
+#[test]
+fn unsupported_version() {
+    let mut bytes = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo")).to_bytes();
+    bytes[0] = 0x02;
+    let err = Cid::try_from(bytes.as_slice()).unwrap_err();
+    assert_eq!(err, Error::UnsupportedVersion(2));
+    assert_eq!(err.to_string(), "Unsupported CID version 2");
+    assert_eq!(Cid::try_from("bai"), Err(Error::UnsupportedVersion(2)));
+    assert_eq!(Version::from(3), Err(Error::UnsupportedVersion(3)));
+}
+
+#[test]
+fn version_conversion() {
+    let v0 = Cid::try_from("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n").unwrap();
+    let v1 = v0.clone().into_v1();
+    assert_eq!(v1.version, Version::V1);
+    assert_eq!(v1.codec, Codec::DagProtobuf);
+    assert_eq!(v1.hash, v0.hash);
+    assert_eq!(
+        v1.to_string(),
+        "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+    );
+    assert_eq!(v1.clone().into_v1(), v1);
+
+    assert_eq!(v1.try_into_v0().unwrap(), v0);
+    assert_eq!(v0.clone().try_into_v0().unwrap(), v0);
+
+    let raw = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo"));
+    assert_eq!(raw.try_into_v0(), Err(Error::InvalidCidV0Codec));
+    let blake = Cid::new_v1(Codec::DagProtobuf, multihash::Blake2b256::digest(b"foo"));
+    assert_eq!(blake.try_into_v0(), Err(Error::InvalidCidV0Multihash));
+}
+
+#[test]
+fn to_string_of_base() {
+    let v0 = Cid::try_from("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n").unwrap();
+    assert_eq!(
+        v0.to_string_of_base(Base::Base58Btc).unwrap(),
+        "QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n"
+    );
+    assert_eq!(
+        v0.to_string_of_base(Base::Base32Lower),
+        Err(Error::InvalidCidV0Base)
+    );
+
+    let v1 = Cid::new_v1(v0.codec, v0.hash);
+    for base in &[Base::Base32Lower, Base::Base58Btc, Base::Base64Url] {
+        let s = v1.to_string_of_base(*base).unwrap();
+        assert_eq!(s.chars().next(), Some(base.code()));
+        assert_eq!(Cid::try_from(s).unwrap(), v1);
+    }
+}
+
+#[test]
+fn remembers_base() {
+    let v1 = Cid::try_from("bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku").unwrap();
+    let s = v1.to_string_of_base(Base::Base58Btc).unwrap();
+
+    let parsed = Cid::try_from(s.as_str()).unwrap();
+    assert_eq!(parsed.base(), Base::Base58Btc);
+    assert_eq!(parsed.to_string(), s);
+    assert_eq!(parsed, v1);
+
+    let v0 = Cid::try_from("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n").unwrap();
+    assert_eq!(v0.base(), Base::Base58Btc);
+    let from_bytes = Cid::try_from(v1.to_bytes()).unwrap();
+    assert_eq!(from_bytes.base(), Base::Base32Lower);
+}
+
+#[test]
+fn base36() {
+    let v1 = Cid::try_from("bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku").unwrap();
+    let s = "k2jmtxx1epa2wl096hsbpuhrz9xhppklonehzwkmskc9rmeb51kwn4ut";
+    assert_eq!(v1.to_string_of_base(Base::Base36Lower).unwrap(), s);
+
+    let parsed = Cid::try_from(s).unwrap();
+    assert_eq!(parsed, v1);
+    assert_eq!(parsed.base(), Base::Base36Lower);
+    assert_eq!(parsed.to_string(), s);
+}
+
+#[test]
+fn case_insensitive_base32() {
+    let lower = "bafybeihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku";
+    let cid = Cid::try_from(lower).unwrap();
+
+    let upper = lower.to_uppercase();
+    let parsed = Cid::try_from(upper.as_str()).unwrap();
+    assert_eq!(parsed, cid);
+    assert_eq!(parsed.base(), Base::Base32Upper);
+    assert_eq!(parsed.to_string(), upper);
+
+    let mixed = format!("b{}", &upper[1..]);
+    assert_eq!(Cid::try_from(mixed).unwrap(), cid);
+}
+
+#[test]
+fn write_bytes() {
+    let h = Sha2_256::digest(b"beep boop");
+    for cid in &[
+        Cid::new_v0(h.clone()).unwrap(),
+        Cid::new_v1(Codec::DagJSON, h),
+    ] {
+        let mut buf = [0u8; 64];
+        let len = cid.write_bytes(&mut buf[..]).unwrap();
+        assert_eq!(len, cid.encoded_len());
+        assert_eq!(&buf[..len], cid.to_bytes().as_slice());
+    }
+}
+
+#[test]
+fn display_matches_multibase() {
+    use multibase::Base::*;
+
+    let bases = [
+        Base2,
+        Base8,
+        Base10,
+        Base16Lower,
+        Base16Upper,
+        Base32Lower,
+        Base32Upper,
+        Base32PadLower,
+        Base32PadUpper,
+        Base32HexLower,
+        Base32HexUpper,
+        Base32HexPadLower,
+        Base32HexPadUpper,
+        Base32Z,
+        Base36Lower,
+        Base36Upper,
+        Base58Flickr,
+        Base58Btc,
+        Base64,
+        Base64Pad,
+        Base64Url,
+        Base64UrlPad,
+    ];
+    let cids = [
+        Cid::new_v1(Codec::Raw, multihash::Identity::digest(b"")),
+        Cid::new_v1(Codec::Raw, multihash::Identity::digest(&[0; 3])),
+        Cid::new_v1(Codec::DagJSON, Sha2_256::digest(b"foo")),
+        Cid::new_v1(Codec::DagCBOR, multihash::Sha2_512::digest(b"foo")),
+        Cid::new_v1(Codec::Raw, multihash::Identity::digest(&[0xff; 300])),
+    ];
+    for cid in cids.iter() {
+        for base in bases.iter() {
+            let s = cid.to_string_of_base(*base).unwrap();
+            assert_eq!(s, multibase::encode(*base, cid.to_bytes()));
+            // The 300 byte digest exceeds the default limits.
+            let cid = Cid::from_str_with_limits(&s, &Limits::NONE).unwrap();
+            assert_eq!(cid.to_string(), s);
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let json = serde_json::to_string(&cid).unwrap();
+    assert_eq!(json, format!("\"{}\"", cid));
+    assert_eq!(serde_json::from_str::<Cid>(&json).unwrap(), cid);
+
+    let bytes = bincode::serialize(&cid).unwrap();
+    assert!(bytes.ends_with(&cid.to_bytes()));
+    assert_eq!(bincode::deserialize::<Cid>(&bytes).unwrap(), cid);
+}
+
+#[cfg(feature = "dag-cbor")]
+#[test]
+fn dag_cbor() {
+    use cid::dag_cbor;
+
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+    let encoded = dag_cbor::encode(&cid);
+    // Tag 42, byte string of 37 bytes, identity multibase prefix.
+    assert_eq!(&encoded[..5], &[0xd8, 0x2a, 0x58, 0x25, 0x00]);
+    assert_eq!(&encoded[5..], cid.to_bytes().as_slice());
+    assert_eq!(dag_cbor::decode(&encoded).unwrap(), cid);
+    assert_eq!(dag_cbor::from_tag_payload(&encoded[4..]).unwrap(), cid);
+
+    // Missing identity prefix.
+    assert_eq!(
+        dag_cbor::from_tag_payload(&cid.to_bytes()),
+        Err(Error::InvalidDagCbor)
+    );
+    // Non-minimal length.
+    let mut long = vec![0xd8, 0x2a, 0x59, 0x00, 0x25];
+    long.extend_from_slice(&encoded[4..]);
+    assert_eq!(dag_cbor::decode(&long), Err(Error::InvalidDagCbor));
+    // Trailing data.
+    let mut trailing = encoded.clone();
+    trailing.push(0x00);
+    assert_eq!(dag_cbor::decode(&trailing), Err(Error::InvalidDagCbor));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn dag_json() {
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let mut json = Vec::new();
+    cid::dag_json::serialize(&cid, &mut serde_json::Serializer::new(&mut json)).unwrap();
+    assert_eq!(json, format!("{{\"/\":\"{}\"}}", cid).into_bytes());
+
+    let mut de = serde_json::Deserializer::from_slice(&json);
+    assert_eq!(cid::dag_json::deserialize(&mut de).unwrap(), cid);
+
+    // Links are always written in base32, whatever base the CID was parsed from.
+    let base58 = cid.to_string_of_base(Base::Base58Btc).unwrap();
+    let parsed = Cid::try_from(base58.as_str()).unwrap();
+    assert_eq!(parsed.to_string(), base58);
+    let mut json = Vec::new();
+    cid::dag_json::serialize(&parsed, &mut serde_json::Serializer::new(&mut json)).unwrap();
+    assert_eq!(json, format!("{{\"/\":\"{}\"}}", cid).into_bytes());
+    let mut de = serde_json::Deserializer::from_slice(&json);
+    assert_eq!(cid::dag_json::deserialize(&mut de).unwrap(), parsed);
+
+    let mut de = serde_json::Deserializer::from_str("{\"/\":\"bafy\",\"a\":1}");
+    assert!(cid::dag_json::deserialize(&mut de).is_err());
+    let mut de = serde_json::Deserializer::from_str("{\"link\":\"bafy\"}");
+    assert!(cid::dag_json::deserialize(&mut de).is_err());
+}
+
+#[cfg(feature = "minicbor")]
+#[test]
+fn minicbor() {
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let encoded = minicbor::to_vec(&cid).unwrap();
+    assert_eq!(&encoded[..5], &[0xd8, 0x2a, 0x58, 0x25, 0x00]);
+    assert_eq!(&encoded[5..], cid.to_bytes().as_slice());
+    assert_eq!(minicbor::decode::<Cid>(&encoded).unwrap(), cid);
+
+    assert!(minicbor::decode::<Cid>(&encoded[2..]).is_err());
+
+    // Byte string without the identity multibase prefix.
+    let mut unprefixed = encoded.clone();
+    unprefixed[3] -= 1;
+    unprefixed.remove(4);
+    assert!(minicbor::decode::<Cid>(&unprefixed).is_err());
+}
+
+#[cfg(feature = "borsh")]
+#[test]
+fn borsh() {
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let encoded = borsh::to_vec(&cid).unwrap();
+    assert_eq!(&encoded[..4], &36u32.to_le_bytes());
+    assert_eq!(&encoded[4..], cid.to_bytes().as_slice());
+    assert_eq!(borsh::from_slice::<Cid>(&encoded).unwrap(), cid);
+
+    assert!(borsh::from_slice::<Cid>(&[2, 0, 0, 0, 1, 2]).is_err());
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn rkyv() {
+    use rkyv::Deserialize;
+
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let bytes = rkyv::to_bytes::<_, 256>(&cid).unwrap();
+    let archived = rkyv::check_archived_root::<Cid>(&bytes).unwrap();
+    assert_eq!(archived.as_bytes(), cid.to_bytes().as_slice());
+    assert_eq!(*archived, cid);
+    assert_eq!(archived.as_cid_ref().unwrap().codec(), Codec::Raw);
+
+    let out: Cid = archived.deserialize(&mut rkyv::Infallible).unwrap();
+    assert_eq!(out, cid);
+
+    let invalid = rkyv::to_bytes::<_, 256>(&vec![1u8, 2, 3]).unwrap();
+    assert!(rkyv::check_archived_root::<Cid>(&invalid).is_err());
+}
+
+#[cfg(feature = "scale")]
+#[test]
+fn scale() {
+    use parity_scale_codec::{Decode, Encode};
+
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let encoded = cid.encode();
+    assert_eq!(encoded, cid.to_bytes().encode());
+    assert_eq!(encoded.len(), cid.size_hint());
+    assert_eq!(Cid::decode(&mut &encoded[..]).unwrap(), cid);
+
+    assert!(Cid::decode(&mut &vec![1u8, 2, 3].encode()[..]).is_err());
+
+    use scale_info::TypeInfo;
+    assert_eq!(Cid::type_info().path.segments, ["cid", "Cid"]);
+}
+
+#[cfg(feature = "prost")]
+#[test]
+fn prost() {
+    use cid::CidProto;
+    use prost::Message;
+
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let encoded = CidProto::from(&cid).encode_to_vec();
+    let decoded = CidProto::decode(encoded.as_slice()).unwrap();
+    assert_eq!(Cid::try_from(decoded).unwrap(), cid);
+
+    let invalid = CidProto { cid: vec![1, 2, 3] };
+    assert!(Cid::try_from(invalid).is_err());
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn arrow() {
+    use cid::arrow::{self, CidArrayBuilder};
+
+    let field = arrow::cid_field("cid", true);
+    assert!(arrow::is_cid_field(&field));
+
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+    let mut builder = CidArrayBuilder::with_capacity(2);
+    builder.append_value(&cid);
+    builder.append_null();
+    let array = builder.finish();
+
+    assert_eq!(arrow::to_cids(&array).unwrap(), vec![Some(cid), None]);
+}
+
+#[cfg(feature = "bson")]
+#[test]
+fn bson() {
+    use ::bson::Bson;
+    use cid::bson::{to_bson, BsonRepr};
+
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let binary = Bson::from(&cid);
+    assert!(matches!(binary, Bson::Binary(_)));
+    assert_eq!(Cid::try_from(binary).unwrap(), cid);
+
+    let string = to_bson(&cid, BsonRepr::String);
+    assert_eq!(string, Bson::String(cid.to_string()));
+    assert_eq!(Cid::try_from(string).unwrap(), cid);
+
+    assert_eq!(Cid::try_from(Bson::Int32(1)), Err(Error::InvalidBson));
+}
+
+#[cfg(feature = "diesel-sqlite")]
+#[test]
+fn diesel_sqlite() {
+    use diesel::prelude::*;
+    use diesel::sql_types::{Binary, Text};
+
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let binary: Cid = diesel::select(cid.clone().into_sql::<Binary>())
+        .get_result(&mut conn)
+        .unwrap();
+    assert_eq!(binary, cid);
+
+    let text: String = diesel::select(cid.clone().into_sql::<Text>())
+        .get_result(&mut conn)
+        .unwrap();
+    assert_eq!(text, cid.to_string());
+}
+
+#[cfg(feature = "rusqlite")]
+#[test]
+fn rusqlite() {
+    let conn = rusqlite::Connection::open_in_memory().unwrap();
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let blob: Cid = conn
+        .query_row("SELECT ?1", [&cid], |row| row.get(0))
+        .unwrap();
+    assert_eq!(blob, cid);
+
+    let text: Cid = conn
+        .query_row("SELECT ?1", [cid.to_string()], |row| row.get(0))
+        .unwrap();
+    assert_eq!(text, cid);
+
+    let err = conn.query_row("SELECT 1", [], |row| row.get::<_, Cid>(0));
+    assert!(err.is_err());
+}
+
+#[cfg(feature = "postgres-types")]
+#[test]
+fn postgres_types() {
+    use bytes::BytesMut;
+    use postgres_types::{FromSql, ToSql, Type};
+
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let mut buf = BytesMut::new();
+    cid.to_sql_checked(&Type::BYTEA, &mut buf).unwrap();
+    assert_eq!(&buf[..], &cid.to_bytes()[..]);
+    assert_eq!(Cid::from_sql(&Type::BYTEA, &buf).unwrap(), cid);
+
+    let mut buf = BytesMut::new();
+    cid.to_sql_checked(&Type::TEXT, &mut buf).unwrap();
+    assert_eq!(&buf[..], cid.to_string().as_bytes());
+    assert_eq!(Cid::from_sql(&Type::TEXT, &buf).unwrap(), cid);
+
+    assert!(cid.to_sql_checked(&Type::INT4, &mut buf).is_err());
+}
+
+#[cfg(feature = "redis")]
+#[test]
+fn redis() {
+    use ::redis::{FromRedisValue, ToRedisArgs, Value};
+    use cid::redis::AsString;
+
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let args = cid.to_redis_args();
+    assert_eq!(args, vec![cid.to_bytes()]);
+    let value = Value::BulkString(args[0].clone());
+    assert_eq!(Cid::from_redis_value(&value).unwrap(), cid);
+
+    let args = AsString(&cid).to_redis_args();
+    assert_eq!(args, vec![cid.to_string().into_bytes()]);
+    let value = Value::BulkString(args[0].clone());
+    assert_eq!(Cid::from_redis_value(&value).unwrap(), cid);
+
+    assert!(Cid::from_redis_value(&Value::Int(1)).is_err());
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn schemars() {
+    let schema = serde_json::to_value(schemars::schema_for!(Cid)).unwrap();
+    assert_eq!(schema["type"], "string");
+    assert_eq!(schema["format"], "cid");
+
+    let pattern = schema["pattern"].as_str().unwrap();
+    assert!(pattern.starts_with('^') && pattern.ends_with('$'));
+}
+
+#[cfg(feature = "utoipa")]
+#[test]
+fn utoipa() {
+    use utoipa::{PartialSchema, ToSchema};
+
+    assert_eq!(<Cid as ToSchema>::name(), "Cid");
+
+    let schema = serde_json::to_value(<Cid as PartialSchema>::schema()).unwrap();
+    assert_eq!(schema["type"], "string");
+    assert_eq!(schema["format"], "cid");
+}
+
+#[cfg(feature = "async-graphql")]
+#[test]
+fn async_graphql() {
+    use async_graphql::{ScalarType, Value};
+
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let value = cid.to_value();
+    assert_eq!(value, Value::String(cid.to_string()));
+    assert!(<Cid as ScalarType>::is_valid(&value));
+    assert_eq!(<Cid as ScalarType>::parse(value).unwrap(), cid);
+
+    let invalid = Value::String("not a cid".to_string());
+    assert!(!<Cid as ScalarType>::is_valid(&invalid));
+    assert!(<Cid as ScalarType>::parse(invalid).is_err());
+    assert!(<Cid as ScalarType>::parse(Value::Number(1.into())).is_err());
+}
+
+#[cfg(feature = "actix")]
+#[test]
+fn actix() {
+    use actix_web::dev::Payload;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use actix_web::FromRequest;
+
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let req = TestRequest::default()
+        .param("cid", cid.to_string())
+        .to_http_request();
+    let extracted = Cid::from_request(&req, &mut Payload::None).into_inner();
+    assert_eq!(extracted.unwrap(), cid);
+
+    let req = TestRequest::default()
+        .param("cid", "invalid")
+        .to_http_request();
+    let err = Cid::from_request(&req, &mut Payload::None)
+        .into_inner()
+        .unwrap_err();
+    assert_eq!(
+        err.as_response_error().status_code(),
+        StatusCode::BAD_REQUEST
+    );
+}
+
+#[cfg(feature = "rocket")]
+#[test]
+fn rocket() {
+    use rocket::form::{FromFormField, ValueField};
+    use rocket::request::FromParam;
+
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+    let string = cid.to_string();
+
+    assert_eq!(Cid::from_param(&string).unwrap(), cid);
+    assert!(Cid::from_param("invalid").is_err());
+
+    assert_eq!(
+        Cid::from_value(ValueField::from_value(&string)).unwrap(),
+        cid
+    );
+    assert!(Cid::from_value(ValueField::from_value("invalid")).is_err());
+}
+
+#[cfg(feature = "clap")]
+#[test]
+fn clap() {
+    use ::clap::error::ErrorKind;
+    use ::clap::{value_parser, Arg, Command};
+
+    let cmd = Command::new("test").arg(Arg::new("cid").value_parser(value_parser!(Cid)));
+    let cid = Cid::try_from("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+
+    let matches = cmd
+        .clone()
+        .try_get_matches_from(["test", &cid.to_string()])
+        .unwrap();
+    assert_eq!(matches.get_one::<Cid>("cid"), Some(&cid));
+
+    let err = cmd
+        .clone()
+        .try_get_matches_from(["test", "b!!!"])
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ValueValidation);
+    assert!(err.to_string().contains("multibase"));
+
+    let err = cmd.try_get_matches_from(["test", "bai"]).unwrap_err();
+    assert!(err.to_string().contains("CID version"));
+}
+
+#[cfg(feature = "wasm")]
+#[test]
+fn wasm() {
+    use cid::wasm::JsCid;
+
+    let string = "bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy";
+    let cid = JsCid::parse(string).unwrap();
+    assert_eq!(cid.to_string(), string);
+    assert_eq!(cid.version(), 1);
+    assert_eq!(cid.codec(), 85.0);
+
+    let from_bytes = JsCid::from_bytes(&cid.to_bytes()).unwrap();
+    assert!(cid.equals(&from_bytes));
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn ffi() {
+    use cid::ffi::*;
+    use std::ffi::{CStr, CString};
+    use std::os::raw::c_int;
+    use std::ptr;
+
+    let string = "bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy";
+    let input = CString::new(string).unwrap();
+
+    unsafe {
+        let mut handle = ptr::null_mut();
+        assert_eq!(cid_parse(input.as_ptr(), &mut handle), CidError::Ok);
+        assert_eq!(cid_version(handle), 1);
+        assert_eq!(cid_codec(handle), 0x55);
+
+        let out = cid_to_string(handle);
+        assert_eq!(CStr::from_ptr(out).to_str().unwrap(), string);
+        cid_string_free(out);
+
+        let mut len = 0;
+        let bytes = cid_to_bytes(handle, &mut len);
+        assert_eq!(len, 36);
+        let mut other = ptr::null_mut();
+        assert_eq!(cid_from_bytes(bytes, len, &mut other), CidError::Ok);
+        assert!(cid_equals(handle, other));
+        cid_bytes_free(bytes, len);
+
+        cid_free(other);
+        cid_free(handle);
+
+        let invalid = CString::new("bai").unwrap();
+        let mut handle = ptr::null_mut();
+        let err = cid_parse(invalid.as_ptr(), &mut handle);
+        assert_eq!(err, CidError::InvalidCidVersion);
+        assert!(handle.is_null());
+        let message = CStr::from_ptr(cid_error_message(err as c_int));
+        assert_eq!(message.to_str().unwrap(), "Unrecognized CID version");
+        let message = CStr::from_ptr(cid_error_message(-1));
+        assert_eq!(message.to_str().unwrap(), "Unknown error");
+        assert_eq!(cid_parse(ptr::null(), &mut handle), CidError::NullPointer);
+
+        let long = CString::new(format!("b{}", "a".repeat(10_000))).unwrap();
+        assert_eq!(
+            cid_parse(long.as_ptr(), &mut handle),
+            CidError::InputTooLong
+        );
+        let mut bytes = vec![0x01, 0x55, 0x00, 0x81, 0x01];
+        bytes.extend_from_slice(&[0; 129]);
+        assert_eq!(
+            cid_from_bytes(bytes.as_ptr(), bytes.len(), &mut handle),
+            CidError::DigestTooLarge
+        );
+    }
+}
+
+#[cfg(feature = "uniffi")]
+#[test]
+fn uniffi() {
+    let string = "bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy";
+    let cid = cid::uniffi::Cid::parse(string.to_string()).unwrap();
+    assert_eq!(cid.to_string(), string);
+    assert_eq!(cid.version(), 1);
+    assert_eq!(cid.codec(), 0x55);
+
+    let from_bytes = cid::uniffi::Cid::from_bytes(cid.to_bytes()).unwrap();
+    assert_eq!(from_bytes, cid);
+
+    assert_eq!(
+        cid::uniffi::Cid::parse("bai".to_string()),
+        Err(Error::UnsupportedVersion(2))
+    );
+}
+
+#[cfg(feature = "libp2p")]
+#[test]
+fn libp2p_peer_id() {
+    use libp2p_identity::PeerId;
+
+    let peer_id = PeerId::from_str("12D3KooWD3eckifWpRn9wQpMG9R9hX3sD158z7EqHWmweQAJU5SA").unwrap();
+    let cid = Cid::from(peer_id);
+    assert_eq!(cid.codec, Codec::Libp2pKey);
+    assert_eq!(PeerId::try_from(&cid), Ok(peer_id));
+
+    let cid = Cid::new_v1(Codec::Raw, Sha2_256::digest(b"foo"));
+    assert_eq!(PeerId::try_from(cid), Err(Error::InvalidPeerId));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn batch() {
+    use cid::batch::{hash_all, parse_all};
+
+    let blocks: Vec<Vec<u8>> = (0..100u8).map(|i| vec![i; 100]).collect();
+    let cids = hash_all(Codec::Raw, multihash::Code::Sha2_256, &blocks);
+    assert_eq!(cids.len(), blocks.len());
+    for (cid, block) in cids.iter().zip(&blocks) {
+        assert_eq!(cid, &Ok(Cid::new_v1(Codec::Raw, Sha2_256::digest(block))));
+    }
+
+    let strings: Vec<String> = cids
+        .iter()
+        .map(|cid| cid.as_ref().unwrap().to_string())
+        .collect();
+    let mut strings: Vec<&str> = strings.iter().map(String::as_str).collect();
+    strings.push("not a cid");
+    let parsed = parse_all(&strings);
+    assert_eq!(&parsed[..100], &cids[..]);
+    assert!(parsed[100].is_err());
+}
+
+#[cfg(feature = "url")]
+#[test]
+fn url() {
+    use cid::IpfsPath;
+    use url::Url;
+
+    let v1 = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    let cid = Cid::try_from(v1).unwrap();
+    for url in &[
+        format!("https://ipfs.io/ipfs/{}/a", v1),
+        format!("https://{}.ipfs.dweb.link/a", v1),
+        format!("ipfs://{}/a", v1),
+    ] {
+        let url = Url::parse(url).unwrap();
+        let path = IpfsPath::try_from(&url).unwrap();
+        assert_eq!(path.cid(), Some(&cid));
+        assert_eq!(path.segments, ["a"]);
+        assert_eq!(Cid::try_from(url), Ok(cid.clone()));
+    }
+
+    let url = Url::parse("ipns://docs.ipfs.tech/concepts").unwrap();
+    assert_eq!(
+        IpfsPath::try_from(&url).unwrap().to_string(),
+        "/ipns/docs.ipfs.tech/concepts"
+    );
+    assert_eq!(Cid::try_from(&url), Err(Error::InvalidPath));
+    let url = Url::parse("ftp://ipfs.io/ipfs/x").unwrap();
+    assert_eq!(Cid::try_from(&url), Err(Error::InvalidUrl));
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_field() {
+    let cid = Cid::try_from("QmdfTbBqBPQ7VNxZEYEj14VmRuZBkqFbiwReogJgS1zR1n").unwrap();
+    assert_eq!(format!("{:?}", cid.as_field()), cid.to_string());
+    tracing::info!(cid = cid.as_field(), "stored block");
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_cid() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let data: Vec<u8> = (0..4096u32).map(|i| (i * 7919 % 251) as u8).collect();
+    let mut u = Unstructured::new(&data);
+    for _ in 0..16 {
+        let cid = Cid::arbitrary(&mut u).unwrap();
+        assert_eq!(Cid::try_from(cid.to_bytes().as_slice()), Ok(cid.clone()));
+        assert_eq!(Cid::try_from(cid.to_string().as_str()), Ok(cid));
+    }
+}
+
+#[cfg(feature = "proptest")]
+proptest::proptest! {
+    #[test]
+    fn proptest_roundtrip(cid in cid::proptest::any_cid()) {
+        proptest::prop_assert_eq!(Cid::try_from(cid.to_bytes().as_slice()), Ok(cid.clone()));
+        proptest::prop_assert_eq!(Cid::try_from(cid.to_string().as_str()), Ok(cid));
+    }
+
+    #[test]
+    fn proptest_any_base(s in cid::proptest::cid_string_any_base()) {
+        let cid = Cid::try_from(s.as_str()).unwrap();
+        proptest::prop_assert_eq!(cid.to_string(), s);
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+#[test]
+fn quickcheck_roundtrip() {
+    use quickcheck::{Arbitrary, QuickCheck};
+
+    fn roundtrip(cid: Cid) -> bool {
+        Cid::try_from(cid.to_bytes().as_slice()) == Ok(cid.clone())
+            && Cid::try_from(cid.to_string().as_str()) == Ok(cid)
+    }
+    QuickCheck::new().quickcheck(roundtrip as fn(Cid) -> bool);
+
+    let cid = Cid::new_v1(Codec::DagProtobuf, Sha2_256::digest(b"foo"));
+    let shrunk: Vec<Cid> = cid.shrink().collect();
+    assert_eq!(shrunk[0], cid.clone().try_into_v0().unwrap());
+    assert!(shrunk[1].digest().iter().all(|byte| *byte == 0));
+    assert!(shrunk.iter().all(|shrunk| roundtrip(shrunk.clone())));
+}
+
+#[cfg(feature = "random")]
+#[test]
+fn random() {
+    use cid::RandomCidConfig;
+    use multihash::Code;
+    use rand::rngs::mock::StepRng;
+    use rand::Rng;
+
+    let mut rng = StepRng::new(1, 0x9e37_79b9_7f4a_7c15);
+    let v0 = Cid::random(&mut rng);
+    assert_eq!(v0.version, Version::V0);
+    assert_ne!(v0, Cid::random(&mut rng));
+
+    let cid = Cid::random_v1(Codec::DagCBOR, Code::Blake2b256, &mut rng);
+    assert_eq!(cid.codec, Codec::DagCBOR);
+    assert_eq!(cid.hash.algorithm(), Code::Blake2b256);
+    assert_eq!(cid.hash.digest().len(), 32);
+    let custom = Cid::random_v1(Codec::Raw, Code::Custom(0x300000), &mut rng);
+    assert_eq!(Cid::try_from(custom.to_bytes()), Ok(custom));
+
+    let cid = RandomCidConfig::new().generate(&mut rng);
+    assert_eq!((cid.version, cid.codec), (Version::V1, Codec::Raw));
+    assert_eq!(cid.hash.algorithm(), Code::Sha2_256);
+    let cid: Cid = rng.gen();
+    assert_eq!((cid.version, cid.codec), (Version::V1, Codec::Raw));
+    assert_eq!(cid.hash.algorithm(), Code::Sha2_256);
+
+    let config = RandomCidConfig::new()
+        .version(Version::V0, 1)
+        .version(Version::V1, 3)
+        .codec(Codec::DagCBOR, 1)
+        .codec(Codec::Raw, 1)
+        .hash_with(Code::Blake2b256, 1)
+        .hash_with(Code::Sha2_256, 0);
+    for _ in 0..100 {
+        let cid = rng.sample(&config);
+        if cid.version == Version::V0 {
+            assert_eq!(cid.hash.algorithm(), Code::Sha2_256);
+        } else {
+            assert!(cid.codec == Codec::DagCBOR || cid.codec == Codec::Raw);
+            assert_eq!(cid.hash.algorithm(), Code::Blake2b256);
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_read_write() {
+    let v0 = Cid::new_v0(Sha2_256::digest(b"foo")).unwrap();
+    let v1 = Cid::new_v1(Codec::DagCBOR, multihash::Sha2_512::digest(b"foo"));
+    let mut buf = Vec::new();
+    assert_eq!(v1.write_async(&mut buf).await.unwrap(), v1.encoded_len());
+    v0.write_async(&mut buf).await.unwrap();
+    buf.extend_from_slice(b"rest");
+
+    let mut reader = buf.as_slice();
+    assert_eq!(Cid::read_async(&mut reader).await.unwrap(), v1);
+    assert_eq!(Cid::read_async(&mut reader).await.unwrap(), v0);
+    assert_eq!(reader, b"rest");
+
+    let mut reader = &buf[..10];
+    let err = Cid::read_async(&mut reader).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[cfg(feature = "futures-io")]
+#[tokio::test]
+async fn futures_io_read_write() {
+    let v0 = Cid::new_v0(Sha2_256::digest(b"foo")).unwrap();
+    let v1 = Cid::new_v1(Codec::DagCBOR, multihash::Sha2_512::digest(b"foo"));
+    let mut buf = Vec::new();
+    assert_eq!(
+        v1.write_futures_io(&mut buf).await.unwrap(),
+        v1.encoded_len()
+    );
+    v0.write_futures_io(&mut buf).await.unwrap();
+    buf.extend_from_slice(b"rest");
+
+    let mut reader = buf.as_slice();
+    assert_eq!(Cid::read_futures_io(&mut reader).await.unwrap(), v1);
+    assert_eq!(Cid::read_futures_io(&mut reader).await.unwrap(), v0);
+    assert_eq!(reader, b"rest");
+}